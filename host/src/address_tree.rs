@@ -0,0 +1,265 @@
+use crate::file_utils::{read_file, read_json_file};
+use proof_core::{
+    merkle::{address_leaf, merkle_levels, merkle_path},
+    proof_io::{MerkleProof, NonMembershipProof},
+};
+use serde::{Deserialize, Serialize};
+
+use std::fs::{create_dir_all, File};
+use std::io::Write;
+use std::path::Path;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Merkle tree of addresses with the path of every leaf, as written by build_address_allowlist and
+// build_sanctions_tree
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AddressTree {
+    pub root: String,
+    pub leaves: Vec<AddressTreeLeaf>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AddressTreeLeaf {
+    pub address: String,
+    pub index: u64,
+    pub path: Vec<String>,
+}
+
+// Reads the addresses of the first column of a CSV file, see parse_address_csv
+pub fn read_address_csv(filename: &str) -> Result<Vec<[u8; 20]>> {
+    parse_address_csv(&read_file(filename)?)
+}
+
+// Reads a plain text list of addresses, see parse_address_list
+pub fn read_address_list(filename: &str) -> Result<Vec<[u8; 20]>> {
+    parse_address_list(&read_file(filename)?)
+}
+
+// Addresses of the first column, in order and without duplicates. Blank lines are skipped, and so is
+// the first line if it is a header.
+pub fn parse_address_csv(contents: &str) -> Result<Vec<[u8; 20]>> {
+    let mut addresses = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        let cell = line
+            .split(',')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .trim_matches('"');
+        if cell.is_empty() || (number == 0 && !cell.starts_with("0x")) {
+            continue;
+        }
+        let address =
+            parse_address(cell).map_err(|error| format!("Line {}: {}", number + 1, error))?;
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+
+    Ok(addresses)
+}
+
+// One address per line, blank lines and # comments are skipped
+pub fn parse_address_list(contents: &str) -> Result<Vec<[u8; 20]>> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(number, line)| (number, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            parse_address(line).map_err(|error| format!("Line {}: {}", number + 1, error).into())
+        })
+        .collect()
+}
+
+pub fn parse_address(address: &str) -> Result<[u8; 20]> {
+    prefix_hex::decode(address.to_lowercase().as_str())
+        .map_err(|_| format!("Invalid address {}", address).into())
+}
+
+// Builds the tree of the addresses' leaves, in the given order
+pub fn build_address_tree(addresses: &[[u8; 20]]) -> Result<AddressTree> {
+    if addresses.is_empty() {
+        return Err("An address tree needs at least one address".into());
+    }
+    let levels = merkle_levels(addresses.iter().map(address_leaf).collect());
+    let leaves = addresses
+        .iter()
+        .enumerate()
+        .map(|(index, address)| AddressTreeLeaf {
+            address: prefix_hex::encode(address.to_vec()),
+            index: index as u64,
+            path: merkle_path(&levels, index)
+                .iter()
+                .map(|sibling| prefix_hex::encode(sibling.to_vec()))
+                .collect(),
+        })
+        .collect();
+
+    Ok(AddressTree {
+        root: prefix_hex::encode(levels[levels.len() - 1][0].to_vec()),
+        leaves,
+    })
+}
+
+// Builds the tree of the sorted, deduplicated addresses, between a zero and an all-ones sentinel
+// leaf so that any other address lies between two adjacent leaves
+pub fn build_sorted_address_tree(addresses: &[[u8; 20]]) -> Result<AddressTree> {
+    let mut sorted = addresses.to_vec();
+    sorted.push([0_u8; 20]);
+    sorted.push([0xff_u8; 20]);
+    sorted.sort();
+    sorted.dedup();
+
+    build_address_tree(&sorted)
+}
+
+impl AddressTree {
+    // Inclusion proof of an address, fails if the address is not in the tree
+    pub fn membership_proof(&self, address: &str) -> Result<MerkleProof> {
+        let address = prefix_hex::encode(parse_address(address)?.to_vec());
+        let leaf = self
+            .leaves
+            .iter()
+            .find(|leaf| leaf.address.to_lowercase() == address)
+            .ok_or_else(|| format!("Address {} is not in the tree", address))?;
+
+        self.proof(leaf)
+    }
+
+    // Proof that an address is not in a sorted tree, from the two leaves around it. Fails if the
+    // address is in the tree.
+    pub fn non_membership_proof(&self, address: &str) -> Result<NonMembershipProof> {
+        let address = parse_address(address)?;
+        let leaves = self
+            .leaves
+            .iter()
+            .map(|leaf| Ok((parse_address(&leaf.address)?, leaf)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // first leaf above the address, preceded by the last one below it
+        let position = leaves
+            .iter()
+            .position(|(leaf_address, _)| *leaf_address >= address)
+            .ok_or("Address is above every leaf of the tree")?;
+        if leaves[position].0 == address {
+            return Err(format!(
+                "Address {} is in the tree",
+                prefix_hex::encode(address.to_vec())
+            )
+            .into());
+        }
+        if position == 0 {
+            return Err("Address is below every leaf of the tree".into());
+        }
+        let (low, low_leaf) = leaves[position - 1];
+        let (high, high_leaf) = leaves[position];
+
+        Ok(NonMembershipProof {
+            low,
+            low_proof: self.proof(low_leaf)?,
+            high,
+            high_proof: self.proof(high_leaf)?,
+        })
+    }
+
+    fn proof(&self, leaf: &AddressTreeLeaf) -> Result<MerkleProof> {
+        let decode = |node: &str| -> Result<[u8; 32]> {
+            prefix_hex::decode(node).map_err(|_| format!("Invalid tree node {}", node).into())
+        };
+
+        Ok(MerkleProof {
+            root: decode(&self.root)?,
+            index: leaf.index,
+            path: leaf
+                .path
+                .iter()
+                .map(|sibling| decode(sibling))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+pub fn read_address_tree(filename: &str) -> Result<AddressTree> {
+    Ok(serde_json::from_value(read_json_file(filename)?)?)
+}
+
+pub fn write_address_tree(tree: &AddressTree, filename: &str) -> Result<()> {
+    let json = serde_json::to_string_pretty(tree)?;
+    if let Some(directory) = Path::new(filename).parent() {
+        create_dir_all(directory)?;
+    }
+    let mut file = File::create(filename)?;
+    file.write_all(json.as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proof_core::merkle::{verify_merkle_proof, verify_non_membership};
+
+    #[test]
+    fn test_parse_address_csv() {
+        let contents = "address,name\n\
+            0x00000000000000000000000000000000000000AA,alice\n\
+            \n\
+            0x00000000000000000000000000000000000000bb\n\
+            0x00000000000000000000000000000000000000aa,alice again\n";
+        let mut first = [0_u8; 20];
+        first[19] = 0xaa;
+        let mut second = [0_u8; 20];
+        second[19] = 0xbb;
+
+        assert_eq!(parse_address_csv(contents).unwrap(), vec![first, second]);
+        assert!(parse_address_csv("0x00\n").is_err());
+    }
+
+    #[test]
+    fn test_build_address_tree() {
+        let addresses: Vec<[u8; 20]> = (1..=3_u8).map(|i| [i; 20]).collect();
+        let tree = build_address_tree(&addresses).unwrap();
+
+        for address in &addresses {
+            let proof = tree
+                .membership_proof(&prefix_hex::encode(address.to_vec()))
+                .unwrap();
+            verify_merkle_proof(&address_leaf(address), &proof);
+        }
+        assert!(tree
+            .membership_proof(&prefix_hex::encode([4_u8; 20].to_vec()))
+            .is_err());
+        assert!(build_address_tree(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_address_list() {
+        let contents = "# sanctioned\n\n  0x00000000000000000000000000000000000000aa  \n";
+        let mut address = [0_u8; 20];
+        address[19] = 0xaa;
+
+        assert_eq!(parse_address_list(contents).unwrap(), vec![address]);
+        assert!(parse_address_list("alice\n").is_err());
+    }
+
+    #[test]
+    fn test_non_membership_proof() {
+        let tree = build_sorted_address_tree(&[[0x30; 20], [0x10; 20], [0x30; 20]]).unwrap();
+        let encode = |address: [u8; 20]| prefix_hex::encode(address.to_vec());
+
+        // sentinels, then 0x10 and 0x30 once each
+        assert_eq!(tree.leaves.len(), 4);
+        assert_eq!(tree.leaves[1].address, encode([0x10; 20]));
+        for address in [[0x01; 20], [0x20; 20], [0xfe; 20]] {
+            let proof = tree.non_membership_proof(&encode(address)).unwrap();
+            verify_non_membership(&address, &proof);
+        }
+        let proof = tree.non_membership_proof(&encode([0x20; 20])).unwrap();
+        assert_eq!((proof.low, proof.high), ([0x10; 20], [0x30; 20]));
+
+        assert!(tree.non_membership_proof(&encode([0x10; 20])).is_err());
+        assert!(tree.non_membership_proof(&encode([0xff; 20])).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use proof_core::eth_utils::format_eth_message;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Challenge issued by a verifier, binding a receipt's signed message to that verifier only
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Challenge {
+    // identity of the verifier issuing the challenge
+    pub verifier: String,
+    // random 32-byte hex nonce
+    pub nonce: String,
+    // unix timestamp (seconds) after which receipts are rejected
+    pub expires_at: u64,
+}
+
+impl Challenge {
+    pub fn new(verifier: &str, validity_secs: u64) -> Result<Self> {
+        let mut nonce = [0_u8; 32];
+        OsRng.fill_bytes(&mut nonce);
+
+        Ok(Challenge {
+            verifier: String::from(verifier),
+            nonce: prefix_hex::encode(nonce.to_vec()),
+            expires_at: unix_timestamp()? + validity_secs,
+        })
+    }
+
+    // Message the prover signs in response to the challenge (before Ethereum formatting)
+    pub fn to_message(&self) -> String {
+        format!(
+            "proof-of-assets challenge\nverifier: {}\nnonce: {}\nexpires at: {}",
+            self.verifier, self.nonce, self.expires_at
+        )
+    }
+
+    // Checks that a receipt's committed message answers this challenge, and that it is still valid
+    pub fn check_message(&self, committed_message: &[u8]) -> Result<()> {
+        if committed_message != format_eth_message(self.to_message()).as_bytes() {
+            return Err("Committed message does not match the challenge".into());
+        }
+
+        self.check_expiry()
+    }
+
+    // Checks that a native key receipt's committed context, which is not signed, is the challenge
+    // message, and that the challenge is still valid
+    pub fn check_context(&self, committed_context: &[u8]) -> Result<()> {
+        if committed_context != self.to_message().as_bytes() {
+            return Err("Committed context does not match the challenge".into());
+        }
+
+        self.check_expiry()
+    }
+
+    fn check_expiry(&self) -> Result<()> {
+        if unix_timestamp()? > self.expires_at {
+            return Err("Challenge has expired".into());
+        }
+
+        Ok(())
+    }
+}
+
+pub fn read_challenge(filename: &str) -> Result<Challenge> {
+    let mut file = File::open(filename)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    Ok(serde_json::from_str(&contents)?)
+}
+
+pub fn write_challenge(challenge: &Challenge, filename: &str) -> Result<()> {
+    let json = serde_json::to_string_pretty(challenge)?;
+    let mut file = File::create(filename)?;
+    file.write_all(json.as_bytes())?;
+
+    Ok(())
+}
+
+fn unix_timestamp() -> Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_read_challenge() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("challenge.json");
+        let file_path = file_path.to_str().unwrap();
+
+        let challenge = Challenge::new("test_verifier", 3600).unwrap();
+        write_challenge(&challenge, file_path).unwrap();
+
+        assert_eq!(read_challenge(file_path).unwrap(), challenge);
+        assert_eq!(challenge.nonce.len(), 66);
+    }
+
+    #[test]
+    fn test_check_message() {
+        let challenge = Challenge::new("test_verifier", 3600).unwrap();
+        let message = format_eth_message(challenge.to_message());
+        let other_challenge = Challenge::new("other_verifier", 3600).unwrap();
+
+        assert!(challenge.check_message(message.as_bytes()).is_ok());
+        assert!(other_challenge.check_message(message.as_bytes()).is_err());
+        assert!(challenge
+            .check_message(challenge.to_message().as_bytes())
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_context() {
+        let challenge = Challenge::new("test_verifier", 3600).unwrap();
+        let other_challenge = Challenge::new("other_verifier", 3600).unwrap();
+
+        assert!(challenge
+            .check_context(challenge.to_message().as_bytes())
+            .is_ok());
+        assert!(other_challenge
+            .check_context(challenge.to_message().as_bytes())
+            .is_err());
+        assert!(challenge
+            .check_context(format_eth_message(challenge.to_message()).as_bytes())
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_message_expired() {
+        let mut challenge = Challenge::new("test_verifier", 0).unwrap();
+        challenge.expires_at -= 1;
+        let message = format_eth_message(challenge.to_message());
+
+        assert!(challenge.check_message(message.as_bytes()).is_err());
+    }
+}
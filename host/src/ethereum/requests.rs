@@ -1,16 +1,36 @@
-use crate::ethereum::rpc::{get_contract_input, get_native_input};
-use methods::{CONTRACT_PROOF_ELF, CONTRACT_PROOF_ID, NATIVE_PROOF_ELF, NATIVE_PROOF_ID};
-use proof_core::proof_io::{ContractProofInput, NativeProofInput, ProofInput};
+use crate::ethereum::rpc::{
+    get_aave_input, get_aggregate_input, get_contract_input, get_ctoken_input,
+    get_inactivity_input, get_lp_input, get_native_input, get_native_key_input, get_owner_input,
+    get_steth_input, get_supply_share_input, get_usd_input, get_vault_input, get_vote_input,
+    get_votes_input,
+};
+use methods::{
+    AAVE_PROOF_ELF, AAVE_PROOF_ID, AGGREGATE_PROOF_ELF, AGGREGATE_PROOF_ID, CONTRACT_PROOF_ELF,
+    CONTRACT_PROOF_ID, CTOKEN_PROOF_ELF, CTOKEN_PROOF_ID, INACTIVITY_PROOF_ELF,
+    INACTIVITY_PROOF_ID, LP_PROOF_ELF, LP_PROOF_ID, NATIVE_KEY_PROOF_ELF, NATIVE_KEY_PROOF_ID,
+    NATIVE_PROOF_ELF, NATIVE_PROOF_ID, OWNER_PROOF_ELF, OWNER_PROOF_ID, STETH_PROOF_ELF,
+    STETH_PROOF_ID, SUPPLY_SHARE_PROOF_ELF, SUPPLY_SHARE_PROOF_ID, USD_PROOF_ELF, USD_PROOF_ID,
+    VAULT_PROOF_ELF, VAULT_PROOF_ID, VOTES_PROOF_ELF, VOTES_PROOF_ID, VOTE_PROOF_ELF,
+    VOTE_PROOF_ID,
+};
+use proof_core::{
+    eth_utils::{check_signature, private_key_to_address, SlotLayout},
+    proof_io::{
+        AaveProofInput, AggregateProofInput, AggregatorLayout, BalanceClaim, BlacklistLayout,
+        CTokenLayout, CTokenProofInput, ContractProofInput, InactivityProofInput, LidoLayout,
+        LpProofInput, MerkleProof, NativeKeyProofInput, NativeProofInput, NonMembershipProof,
+        OwnerProofInput, PairLayout, Predicate, ProofInput, StethProofInput, SupplyShareProofInput,
+        UsdProofInput, VaultProofInput, VoteProofInput, VotesProofInput,
+    },
+};
 use serde::{Deserialize, Serialize};
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 pub trait Request {
     type Input: ProofInput;
-    fn get_user_address(&self) -> &str;
-    fn get_signature(&self) -> &str;
-    fn get_message(&self) -> &str;
-    fn get_expected_balance(&self) -> &u64;
+    fn get_user_addresses(&self) -> Vec<&str>;
+    fn check_ownership(&self) -> Result<bool>;
     fn get_proof_id(&self) -> [u32; 8];
     fn get_proof_elf(&self) -> &[u8];
     fn get_proof_input(&self) -> Result<Self::Input>;
@@ -24,7 +44,12 @@ pub struct NativeRequest {
     pub block_number: String,
     pub signature: String,
     pub message: String,
-    pub expected_balance: u64,
+    pub balance_claim: BalanceClaim,
+    pub address_salt: Option<String>,
+    pub balance_blinding: Option<String>,
+    pub predicate: Option<Predicate>,
+    pub address_allowlist: Option<MerkleProof>,
+    pub sanctions: Option<NonMembershipProof>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -34,28 +59,231 @@ pub struct ContractRequest {
     pub block_number: String,
     pub signature: String,
     pub message: String,
-    pub expected_balance: u64,
+    pub balance_claim: BalanceClaim,
     pub contract_address: String,
     pub balance_slot: String,
+    pub layout: SlotLayout,
+    pub address_salt: Option<String>,
+    pub balance_blinding: Option<String>,
+    // where the token records frozen accounts, to prove the holder is not one of them
+    pub blacklist: Option<BlacklistLayout>,
+    pub address_allowlist: Option<MerkleProof>,
+    pub sanctions: Option<NonMembershipProof>,
 }
 
-impl Request for NativeRequest {
-    type Input = NativeProofInput;
+// Holds a decrypted private key: deliberately neither Debug nor Serialize
+#[derive(Clone)]
+pub struct NativeKeyRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub private_key: [u8; 32],
+    pub context: String,
+    pub balance_claim: BalanceClaim,
+}
 
-    fn get_user_address(&self) -> &str {
-        &self.user_address
-    }
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SignedAccount {
+    pub user_address: String,
+    pub signature: String,
+}
 
-    fn get_signature(&self) -> &str {
-        &self.signature
-    }
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AggregateRequest {
+    pub provider: String,
+    pub block_number: String,
+    pub message: String,
+    pub balance_claim: BalanceClaim,
+    pub accounts: Vec<SignedAccount>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct InactivityRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub start_block: String,
+    pub end_block: String,
+    pub signature: String,
+    pub message: String,
+    pub expected_balance: [u8; 32],
+}
 
-    fn get_message(&self) -> &str {
-        &self.message
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct OwnerRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    pub contract_address: String,
+    pub owner_slot: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TokenAsset {
+    pub contract_address: String,
+    pub balance_slot: String,
+    pub layout: SlotLayout,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UsdRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    // valued token, the native asset if none
+    pub token: Option<TokenAsset>,
+    pub asset_decimals: u8,
+    pub aggregator_address: String,
+    pub aggregator_layout: AggregatorLayout,
+    pub feed_decimals: u8,
+    // maximum age of the price, in seconds
+    pub max_age: u64,
+    pub usd_threshold: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SupplyShareRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    pub contract_address: String,
+    pub balance_slot: String,
+    pub layout: SlotLayout,
+    pub total_supply_slot: String,
+    pub bps: u16,
+}
+
+// Storage backing a vault's total assets
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum VaultAssetsSource {
+    // slots of the vault summed into its total assets
+    Stored(Vec<String>),
+    // underlying token, whose balance of the vault is its total assets
+    Held(TokenAsset),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VaultRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    pub vault_address: String,
+    pub balance_slot: String,
+    pub layout: SlotLayout,
+    pub total_supply_slot: String,
+    pub assets: VaultAssetsSource,
+    pub decimals_offset: u8,
+    pub threshold: [u8; 32],
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StethRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    pub steth_address: String,
+    pub lido_layout: LidoLayout,
+    // wstETH token, whose balance is added to the holder's shares
+    pub wrapped: Option<TokenAsset>,
+    pub threshold: [u8; 32],
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AaveRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    pub pool_address: String,
+    pub reserves_slot: String,
+    pub asset_address: String,
+    // read from the reserve data if none
+    pub a_token_address: Option<String>,
+    pub user_state_slot: String,
+    pub threshold: [u8; 32],
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LpRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    pub pair_address: String,
+    pub pair_layout: PairLayout,
+    pub threshold0: [u8; 32],
+    pub threshold1: [u8; 32],
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CTokenRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    pub ctoken_address: String,
+    pub ctoken_layout: CTokenLayout,
+    // underlying token, ether (cETH) if none
+    pub underlying: Option<TokenAsset>,
+    pub threshold: [u8; 32],
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VotesRequest {
+    pub provider: String,
+    pub user_address: String,
+    pub block_number: String,
+    pub signature: String,
+    pub message: String,
+    pub token_address: String,
+    pub checkpoints_slot: String,
+    pub key_bytes: u8,
+    pub threshold: [u8; 32],
+}
+
+// How a voter proves ownership, with the key of a keystore or a signature of the vote message
+#[derive(Clone)]
+pub enum VoteOwnership {
+    PrivateKey([u8; 32]),
+    Signature { signature: String, message: String },
+}
+
+// Holds a decrypted private key: deliberately neither Debug nor Serialize
+#[derive(Clone)]
+pub struct VoteRequest {
+    pub provider: String,
+    pub user_address: String,
+    // snapshot block of the poll
+    pub block_number: String,
+    pub ownership: VoteOwnership,
+    // weighting token, the native asset if none
+    pub token: Option<TokenAsset>,
+    pub poll_id: [u8; 32],
+    pub choice: u32,
+    pub tiers: Option<Vec<[u8; 32]>>,
+}
+
+impl Request for NativeRequest {
+    type Input = NativeProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
     }
 
-    fn get_expected_balance(&self) -> &u64 {
-        &self.expected_balance
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
     }
 
     fn get_proof_id(&self) -> [u32; 8] {
@@ -78,20 +306,12 @@ impl Request for NativeRequest {
 impl Request for ContractRequest {
     type Input = ContractProofInput;
 
-    fn get_user_address(&self) -> &str {
-        &self.user_address
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
     }
 
-    fn get_signature(&self) -> &str {
-        &self.signature
-    }
-
-    fn get_message(&self) -> &str {
-        &self.message
-    }
-
-    fn get_expected_balance(&self) -> &u64 {
-        &self.expected_balance
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
     }
 
     fn get_proof_id(&self) -> [u32; 8] {
@@ -113,3 +333,411 @@ impl Request for ContractRequest {
         )
     }
 }
+
+impl Request for NativeKeyRequest {
+    type Input = NativeKeyProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        let address = prefix_hex::encode(private_key_to_address(&self.private_key)?.to_vec());
+        Ok(address == self.user_address.to_lowercase())
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        NATIVE_KEY_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        NATIVE_KEY_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_native_key_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        String::from("latest account proof")
+    }
+}
+
+impl Request for AggregateRequest {
+    type Input = AggregateProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        self.accounts
+            .iter()
+            .map(|account| account.user_address.as_str())
+            .collect()
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        for account in &self.accounts {
+            if !check_signature(&account.signature, &self.message, &account.user_address)? {
+                return Ok(false);
+            }
+        }
+        Ok(!self.accounts.is_empty())
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        AGGREGATE_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        AGGREGATE_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_aggregate_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!("latest account proofs of {} accounts", self.accounts.len())
+    }
+}
+
+impl Request for InactivityRequest {
+    type Input = InactivityProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        INACTIVITY_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        INACTIVITY_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_inactivity_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "account proofs at blocks {} and {}",
+            &self.start_block, &self.end_block
+        )
+    }
+}
+
+impl Request for OwnerRequest {
+    type Input = OwnerProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        OWNER_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        OWNER_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_owner_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "latest owner slot proof for contract {}",
+            &self.contract_address
+        )
+    }
+}
+
+impl Request for UsdRequest {
+    type Input = UsdProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        USD_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        USD_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_usd_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "latest balance and price proofs from aggregator {}",
+            &self.aggregator_address
+        )
+    }
+}
+
+impl Request for SupplyShareRequest {
+    type Input = SupplyShareProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        SUPPLY_SHARE_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        SUPPLY_SHARE_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_supply_share_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "latest balance and total supply proofs for contract {}",
+            &self.contract_address
+        )
+    }
+}
+
+impl Request for VaultRequest {
+    type Input = VaultProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        VAULT_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        VAULT_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_vault_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "latest share balance and total assets proofs for vault {}",
+            &self.vault_address
+        )
+    }
+}
+
+impl Request for StethRequest {
+    type Input = StethProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        STETH_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        STETH_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_steth_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "latest shares and total pooled ether proofs for stETH {}",
+            &self.steth_address
+        )
+    }
+}
+
+impl Request for AaveRequest {
+    type Input = AaveProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        AAVE_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        AAVE_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_aave_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "latest scaled balance and reserve data proofs for asset {}",
+            &self.asset_address
+        )
+    }
+}
+
+impl Request for LpRequest {
+    type Input = LpProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        LP_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        LP_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_lp_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "latest LP balance and reserves proofs for pair {}",
+            &self.pair_address
+        )
+    }
+}
+
+impl Request for CTokenRequest {
+    type Input = CTokenProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        CTOKEN_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        CTOKEN_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_ctoken_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!(
+            "latest balance and exchange rate proofs for cToken {}",
+            &self.ctoken_address
+        )
+    }
+}
+
+impl Request for VotesRequest {
+    type Input = VotesProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        check_signature(&self.signature, &self.message, &self.user_address)
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        VOTES_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        VOTES_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_votes_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!("latest checkpoint proofs for token {}", &self.token_address)
+    }
+}
+
+impl Request for VoteRequest {
+    type Input = VoteProofInput;
+
+    fn get_user_addresses(&self) -> Vec<&str> {
+        vec![&self.user_address]
+    }
+
+    fn check_ownership(&self) -> Result<bool> {
+        match &self.ownership {
+            VoteOwnership::PrivateKey(private_key) => {
+                let address = prefix_hex::encode(private_key_to_address(private_key)?.to_vec());
+                Ok(address == self.user_address.to_lowercase())
+            }
+            VoteOwnership::Signature { signature, message } => {
+                check_signature(signature, message, &self.user_address)
+            }
+        }
+    }
+
+    fn get_proof_id(&self) -> [u32; 8] {
+        VOTE_PROOF_ID
+    }
+
+    fn get_proof_elf(&self) -> &[u8] {
+        VOTE_PROOF_ELF
+    }
+
+    fn get_proof_input(&self) -> Result<Self::Input> {
+        get_vote_input(self)
+    }
+
+    fn get_description(&self) -> String {
+        format!("balance proof at snapshot block {}", &self.block_number)
+    }
+}
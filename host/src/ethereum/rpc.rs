@@ -1,13 +1,25 @@
-use crate::ethereum::requests::{ContractRequest, NativeRequest};
+use crate::ethereum::requests::{
+    AaveRequest, AggregateRequest, CTokenRequest, ContractRequest, InactivityRequest, LpRequest,
+    NativeKeyRequest, NativeRequest, OwnerRequest, StethRequest, SupplyShareRequest, TokenAsset,
+    UsdRequest, VaultAssetsSource, VaultRequest, VoteOwnership, VoteRequest, VotesRequest,
+};
 use prefix_hex::{decode, encode, FromHexPrefixed};
 use proof_core::{
-    eth_utils::{EthGetBlockBody, EthGetProofBody},
-    proof_io::{ContractProofInput, NativeProofInput},
+    eth_utils::{
+        array_element_slot, mapping_slot, packed_field, slot_offset, word_mapping_slot,
+        EthGetBlockBody, EthGetProofBody, SlotLayout,
+    },
+    proof_io::{
+        AaveProofInput, AccountOwnership, AggregateProofInput, AssetProof, BlacklistLayout,
+        BlacklistProof, CTokenProofInput, CashProof, ContractProofInput, InactivityProofInput,
+        LpProofInput, NativeKeyProofInput, NativeProofInput, OwnerProofInput, StethProofInput,
+        SupplyShareProofInput, UsdProofInput, VaultAssetsProof, VaultProofInput, VoteProofInput,
+        VoteWitness, VotesProofInput, WrappedSharesProof,
+    },
+    proof_utils::slot_to_address,
 };
 
-use concat_arrays::concat_arrays;
 use serde_json::{Map, Value};
-use sha3::{Digest, Keccak256};
 use ureq::{agent, Agent};
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
@@ -43,6 +55,50 @@ impl EthereumRpcClient {
         Ok(parse_block_response(block_response)?)
     }
 
+    pub fn get_balance(&self, address: &str, block_number: &str) -> Result<String> {
+        // eth_getBalance POST request to the JSON-RPC provider
+        let result: Value = self
+            .client
+            .post(self.provider.as_str())
+            .send_json(build_request_payload(
+                "eth_getBalance",
+                &[serde_json::json!(address), serde_json::json!(block_number)],
+            ))?
+            .into_json()?;
+
+        Ok(result["result"]
+            .as_str()
+            .expect("eth_getBalance call failed")
+            .to_owned())
+    }
+
+    pub fn get_storage_at(
+        &self,
+        address: &str,
+        slot: &str,
+        block_number: &str,
+    ) -> Result<[u8; 32]> {
+        // eth_getStorageAt POST request to the JSON-RPC provider
+        let result: Value = self
+            .client
+            .post(self.provider.as_str())
+            .send_json(build_request_payload(
+                "eth_getStorageAt",
+                &[
+                    serde_json::json!(address),
+                    serde_json::json!(slot),
+                    serde_json::json!(block_number),
+                ],
+            ))?
+            .into_json()?;
+
+        Ok(decode_hex_string(
+            result["result"]
+                .as_str()
+                .expect("eth_getStorageAt call failed"),
+        ))
+    }
+
     pub fn get_proof(
         &self,
         address: &str,
@@ -83,6 +139,81 @@ pub fn get_native_input(input: &NativeRequest) -> Result<NativeProofInput> {
         block_hash: block_response.block_hash,
         account_proof: proof_response.account_proof,
         user_address: decode_hex_string(&input.user_address),
+        balance_claim: input.balance_claim.clone(),
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+        address_salt: input.address_salt.as_deref().map(decode_hex_string),
+        balance_blinding: input.balance_blinding.as_deref().map(decode_hex_string),
+        predicate: input.predicate.clone(),
+        address_allowlist: input.address_allowlist.clone(),
+        sanctions: input.sanctions.clone(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_native_key_input(input: &NativeKeyRequest) -> Result<NativeKeyProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // for the proof block number, we pass the previous call's response to make sure
+    // they are the same (e.g. if "latest" was used there could be a discrepancy)
+    let proof_response = client.get_proof(&input.user_address, &block_response.number, "")?;
+
+    let result = NativeKeyProofInput {
+        private_key: input.private_key,
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        account_proof: proof_response.account_proof,
+        balance_claim: input.balance_claim.clone(),
+        context: input.context.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_aggregate_input(input: &AggregateRequest) -> Result<AggregateProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+
+    let mut accounts = Vec::with_capacity(input.accounts.len());
+    for account in &input.accounts {
+        // all proofs are requested against the same block number
+        let proof_response = client.get_proof(&account.user_address, &block_response.number, "")?;
+        accounts.push(AccountOwnership {
+            user_address: decode_hex_string(&account.user_address),
+            account_proof: proof_response.account_proof,
+            signature: decode_hex_string(&account.signature),
+        });
+    }
+    // the guest rejects unsorted addresses, which prevents counting an account twice
+    accounts.sort_by_key(|account| account.user_address);
+
+    let result = AggregateProofInput {
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        accounts,
+        balance_claim: input.balance_claim.clone(),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_inactivity_input(input: &InactivityRequest) -> Result<InactivityProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let start_block = client.get_block_by_number(&input.start_block)?;
+    let end_block = client.get_block_by_number(&input.end_block)?;
+    let start_proof = client.get_proof(&input.user_address, &start_block.number, "")?;
+    let end_proof = client.get_proof(&input.user_address, &end_block.number, "")?;
+
+    let result = InactivityProofInput {
+        user_address: decode_hex_string(&input.user_address),
+        start_header: start_block.header_rlp,
+        end_header: end_block.header_rlp,
+        start_block_hash: start_block.block_hash,
+        end_block_hash: end_block.block_hash,
+        start_account_proof: start_proof.account_proof,
+        end_account_proof: end_proof.account_proof,
         expected_balance: input.expected_balance,
         signature: decode_hex_string(&input.signature),
         message: input.message.as_bytes().to_vec(),
@@ -91,28 +222,180 @@ pub fn get_native_input(input: &NativeRequest) -> Result<NativeProofInput> {
     Ok(result)
 }
 
+// Returns whether the address holds a non-zero native balance at the given block
+pub fn has_balance(provider: &str, address: &str, block_number: &str) -> Result<bool> {
+    let client = EthereumRpcClient::new(provider);
+    let balance = client.get_balance(address, block_number)?;
+
+    Ok(!balance
+        .trim_start_matches("0x")
+        .trim_start_matches('0')
+        .is_empty())
+}
+
 pub fn get_contract_input(input: &ContractRequest) -> Result<ContractProofInput> {
     let client = EthereumRpcClient::new(&input.provider);
     let block_response = client.get_block_by_number(&input.block_number)?;
     // for the proof block number, we pass the previous call's response to make sure
     // they are the same (e.g. if "latest" was used there could be a discrepancy)
-    let key_prehash: [u8; 64] = concat_arrays!(
-        [0_u8; 12],
-        decode_hex_string::<[u8; 20]>(&input.user_address),
-        decode_hex_string::<[u8; 32]>(&input.balance_slot)
+    let slot = mapping_slot(
+        input.layout,
+        &decode_hex_string(&input.user_address),
+        &decode_hex_string(&input.balance_slot),
     );
-    let key: String = encode(Keccak256::digest(key_prehash).to_vec());
+    let key: String = encode(slot.to_vec());
 
     let proof_response = client.get_proof(&input.contract_address, &block_response.number, &key)?;
 
+    // a blacklist mapping entry needs its own storage proof, a flag in the balance slot does not
+    let blacklist = match input.blacklist {
+        Some(BlacklistLayout::Mapping(blacklist_slot)) => {
+            let slot = mapping_slot(
+                input.layout,
+                &decode_hex_string(&input.user_address),
+                &blacklist_slot,
+            );
+            let response = client.get_proof(
+                &input.contract_address,
+                &block_response.number,
+                &encode(slot.to_vec()),
+            )?;
+            Some(BlacklistProof::Mapping {
+                blacklist_slot,
+                storage_proof: response.storage_proof,
+            })
+        }
+        Some(BlacklistLayout::BalanceHighBit) => Some(BlacklistProof::BalanceHighBit),
+        None => None,
+    };
+
     let result = ContractProofInput {
-        storage_hash: proof_response.storage_hash,
+        root: block_response.storage_hash,
         block_hash: block_response.block_hash,
+        account_proof: proof_response.account_proof,
         storage_proof: proof_response.storage_proof,
         user_address: decode_hex_string(&input.user_address),
         contract_address: decode_hex_string(&input.contract_address),
         balance_slot: decode_hex_string(&input.balance_slot),
-        expected_balance: input.expected_balance,
+        layout: input.layout,
+        balance_claim: input.balance_claim.clone(),
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+        address_salt: input.address_salt.as_deref().map(decode_hex_string),
+        balance_blinding: input.balance_blinding.as_deref().map(decode_hex_string),
+        blacklist,
+        address_allowlist: input.address_allowlist.clone(),
+        sanctions: input.sanctions.clone(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_owner_input(input: &OwnerRequest) -> Result<OwnerProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // unlike a mapping entry, the owner slot is read as is
+    let proof_response = client.get_proof(
+        &input.contract_address,
+        &block_response.number,
+        &input.owner_slot,
+    )?;
+
+    let result = OwnerProofInput {
+        contract_address: decode_hex_string(&input.contract_address),
+        owner_slot: decode_hex_string(&input.owner_slot),
+        user_address: decode_hex_string(&input.user_address),
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        account_proof: proof_response.account_proof,
+        storage_proof: proof_response.storage_proof,
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_usd_input(input: &UsdRequest) -> Result<UsdProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // every proof is requested against the same block, so that they share the state root
+    let block_number = &block_response.number;
+    let user_address: [u8; 20] = decode_hex_string(&input.user_address);
+    let asset = get_asset_proof(&client, &input.token, &input.user_address, block_number)?;
+
+    // the latest round ID is read first, as it locates the transmission slot
+    let layout = &input.aggregator_layout;
+    let hot_vars_slot = encode(layout.hot_vars_slot.to_vec());
+    let hot_vars =
+        client.get_storage_at(&input.aggregator_address, &hot_vars_slot, block_number)?;
+    let round_id = packed_field(&hot_vars, layout.round_id_offset as usize, 4).as_u32();
+    let mut round_key = [0_u8; 32];
+    round_key[28..].copy_from_slice(&round_id.to_be_bytes());
+    let transmission_slot =
+        word_mapping_slot(SlotLayout::Solidity, &round_key, &layout.transmissions_slot);
+
+    let hot_vars_response =
+        client.get_proof(&input.aggregator_address, block_number, &hot_vars_slot)?;
+    let transmission_response = client.get_proof(
+        &input.aggregator_address,
+        block_number,
+        &encode(transmission_slot.to_vec()),
+    )?;
+
+    let result = UsdProofInput {
+        user_address,
+        header: block_response.header_rlp,
+        block_hash: block_response.block_hash,
+        asset,
+        asset_decimals: input.asset_decimals,
+        aggregator_address: decode_hex_string(&input.aggregator_address),
+        aggregator_layout: input.aggregator_layout.clone(),
+        feed_decimals: input.feed_decimals,
+        aggregator_proof: hot_vars_response.account_proof,
+        hot_vars_proof: hot_vars_response.storage_proof,
+        transmission_proof: transmission_response.storage_proof,
+        max_age: input.max_age,
+        usd_threshold: input.usd_threshold,
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_supply_share_input(input: &SupplyShareRequest) -> Result<SupplyShareProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // both slots are proven against the same block, hence the same storage root
+    let block_number = &block_response.number;
+    let user_address: [u8; 20] = decode_hex_string(&input.user_address);
+    let balance_slot = decode_hex_string(&input.balance_slot);
+    let slot = mapping_slot(input.layout, &user_address, &balance_slot);
+
+    let balance_response = client.get_proof(
+        &input.contract_address,
+        block_number,
+        &encode(slot.to_vec()),
+    )?;
+    let total_supply_response = client.get_proof(
+        &input.contract_address,
+        block_number,
+        &input.total_supply_slot,
+    )?;
+
+    let result = SupplyShareProofInput {
+        contract_address: decode_hex_string(&input.contract_address),
+        balance_slot,
+        layout: input.layout,
+        total_supply_slot: decode_hex_string(&input.total_supply_slot),
+        user_address,
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        account_proof: balance_response.account_proof,
+        balance_proof: balance_response.storage_proof,
+        total_supply_proof: total_supply_response.storage_proof,
+        bps: input.bps,
         signature: decode_hex_string(&input.signature),
         message: input.message.as_bytes().to_vec(),
     };
@@ -120,6 +403,425 @@ pub fn get_contract_input(input: &ContractRequest) -> Result<ContractProofInput>
     Ok(result)
 }
 
+pub fn get_vault_input(input: &VaultRequest) -> Result<VaultProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // every proof is requested against the same block, so that they share the state root
+    let block_number = &block_response.number;
+    let user_address: [u8; 20] = decode_hex_string(&input.user_address);
+    let vault_address: [u8; 20] = decode_hex_string(&input.vault_address);
+    let balance_slot = decode_hex_string(&input.balance_slot);
+    let slot = mapping_slot(input.layout, &user_address, &balance_slot);
+
+    let balance_response =
+        client.get_proof(&input.vault_address, block_number, &encode(slot.to_vec()))?;
+    let total_supply_response =
+        client.get_proof(&input.vault_address, block_number, &input.total_supply_slot)?;
+
+    let assets = match &input.assets {
+        VaultAssetsSource::Stored(slots) => {
+            let mut storage_proofs = Vec::new();
+            for slot in slots {
+                storage_proofs.push(
+                    client
+                        .get_proof(&input.vault_address, block_number, slot)?
+                        .storage_proof,
+                );
+            }
+            VaultAssetsProof::Stored {
+                slots: slots.iter().map(|slot| decode_hex_string(slot)).collect(),
+                storage_proofs,
+            }
+        }
+        VaultAssetsSource::Held(underlying) => {
+            // the vault is the holder of the underlying token
+            let underlying_slot = decode_hex_string(&underlying.balance_slot);
+            let slot = mapping_slot(underlying.layout, &vault_address, &underlying_slot);
+            let proof_response = client.get_proof(
+                &underlying.contract_address,
+                block_number,
+                &encode(slot.to_vec()),
+            )?;
+            VaultAssetsProof::Held {
+                underlying_address: decode_hex_string(&underlying.contract_address),
+                balance_slot: underlying_slot,
+                layout: underlying.layout,
+                account_proof: proof_response.account_proof,
+                storage_proof: proof_response.storage_proof,
+            }
+        }
+    };
+
+    let result = VaultProofInput {
+        vault_address,
+        balance_slot,
+        layout: input.layout,
+        total_supply_slot: decode_hex_string(&input.total_supply_slot),
+        user_address,
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        account_proof: balance_response.account_proof,
+        balance_proof: balance_response.storage_proof,
+        total_supply_proof: total_supply_response.storage_proof,
+        assets,
+        decimals_offset: input.decimals_offset,
+        threshold: input.threshold,
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_steth_input(input: &StethRequest) -> Result<StethProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // every proof is requested against the same block, so that they share the state root
+    let block_number = &block_response.number;
+    let user_address: [u8; 20] = decode_hex_string(&input.user_address);
+    let layout = &input.lido_layout;
+    let shares_slot = mapping_slot(SlotLayout::Solidity, &user_address, &layout.shares_slot);
+
+    let shares_response = client.get_proof(
+        &input.steth_address,
+        block_number,
+        &encode(shares_slot.to_vec()),
+    )?;
+    let storage_proof = |position: &[u8; 32]| -> Result<Vec<Vec<u8>>> {
+        Ok(client
+            .get_proof(
+                &input.steth_address,
+                block_number,
+                &encode(position.to_vec()),
+            )?
+            .storage_proof)
+    };
+
+    let wrapped = match &input.wrapped {
+        Some(wrapped) => {
+            let balance_slot = decode_hex_string(&wrapped.balance_slot);
+            let slot = mapping_slot(wrapped.layout, &user_address, &balance_slot);
+            let proof_response = client.get_proof(
+                &wrapped.contract_address,
+                block_number,
+                &encode(slot.to_vec()),
+            )?;
+            Some(WrappedSharesProof {
+                wsteth_address: decode_hex_string(&wrapped.contract_address),
+                balance_slot,
+                layout: wrapped.layout,
+                account_proof: proof_response.account_proof,
+                storage_proof: proof_response.storage_proof,
+            })
+        }
+        None => None,
+    };
+
+    let result = StethProofInput {
+        steth_address: decode_hex_string(&input.steth_address),
+        lido_layout: layout.clone(),
+        user_address,
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        account_proof: shares_response.account_proof,
+        shares_proof: shares_response.storage_proof,
+        total_shares_proof: storage_proof(&layout.total_shares_position)?,
+        buffered_ether_proof: storage_proof(&layout.buffered_ether_position)?,
+        cl_balance_proof: storage_proof(&layout.cl_balance_position)?,
+        deposited_validators_proof: storage_proof(&layout.deposited_validators_position)?,
+        cl_validators_proof: storage_proof(&layout.cl_validators_position)?,
+        wrapped,
+        threshold: input.threshold,
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_aave_input(input: &AaveRequest) -> Result<AaveProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // every proof is requested against the same block, so that they share the state root
+    let block_number = &block_response.number;
+    let user_address: [u8; 20] = decode_hex_string(&input.user_address);
+    let asset_address: [u8; 20] = decode_hex_string(&input.asset_address);
+    let reserves_slot = decode_hex_string(&input.reserves_slot);
+    let reserve_slot = mapping_slot(SlotLayout::Solidity, &asset_address, &reserves_slot);
+    let reserve_proof = |offset: u64| -> Result<EthGetProofBody> {
+        client.get_proof(
+            &input.pool_address,
+            block_number,
+            &encode(slot_offset(&reserve_slot, offset).to_vec()),
+        )
+    };
+
+    // the reserve data locates the aToken unless it is given
+    let a_token_address = match &input.a_token_address {
+        Some(a_token_address) => decode_hex_string(a_token_address),
+        None => slot_to_address(&client.get_storage_at(
+            &input.pool_address,
+            &encode(slot_offset(&reserve_slot, 4).to_vec()),
+            block_number,
+        )?),
+    };
+    let user_state_slot = decode_hex_string(&input.user_state_slot);
+    let slot = mapping_slot(SlotLayout::Solidity, &user_address, &user_state_slot);
+    let balance_response = client.get_proof(
+        &encode(a_token_address.to_vec()),
+        block_number,
+        &encode(slot.to_vec()),
+    )?;
+    let liquidity_index_response = reserve_proof(1)?;
+
+    let result = AaveProofInput {
+        pool_address: decode_hex_string(&input.pool_address),
+        reserves_slot,
+        asset_address,
+        a_token_address,
+        user_state_slot,
+        user_address,
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        a_token_account_proof: balance_response.account_proof,
+        balance_proof: balance_response.storage_proof,
+        pool_account_proof: liquidity_index_response.account_proof,
+        liquidity_index_proof: liquidity_index_response.storage_proof,
+        last_update_proof: reserve_proof(3)?.storage_proof,
+        a_token_slot_proof: reserve_proof(4)?.storage_proof,
+        threshold: input.threshold,
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_lp_input(input: &LpRequest) -> Result<LpProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // every proof is requested against the same block, so that they share the state root
+    let block_number = &block_response.number;
+    let user_address: [u8; 20] = decode_hex_string(&input.user_address);
+    let layout = &input.pair_layout;
+    let slot = mapping_slot(SlotLayout::Solidity, &user_address, &layout.balance_slot);
+
+    let balance_response =
+        client.get_proof(&input.pair_address, block_number, &encode(slot.to_vec()))?;
+    let storage_proof = |slot: &[u8; 32]| -> Result<Vec<Vec<u8>>> {
+        Ok(client
+            .get_proof(&input.pair_address, block_number, &encode(slot.to_vec()))?
+            .storage_proof)
+    };
+
+    let result = LpProofInput {
+        pair_address: decode_hex_string(&input.pair_address),
+        pair_layout: layout.clone(),
+        user_address,
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        account_proof: balance_response.account_proof,
+        balance_proof: balance_response.storage_proof,
+        total_supply_proof: storage_proof(&layout.total_supply_slot)?,
+        token0_proof: storage_proof(&layout.token0_slot)?,
+        token1_proof: storage_proof(&layout.token1_slot)?,
+        reserves_proof: storage_proof(&layout.reserves_slot)?,
+        threshold0: input.threshold0,
+        threshold1: input.threshold1,
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_ctoken_input(input: &CTokenRequest) -> Result<CTokenProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // every proof is requested against the same block, so that they share the state root
+    let block_number = &block_response.number;
+    let user_address: [u8; 20] = decode_hex_string(&input.user_address);
+    let ctoken_address: [u8; 20] = decode_hex_string(&input.ctoken_address);
+    let layout = &input.ctoken_layout;
+    let slot = mapping_slot(SlotLayout::Solidity, &user_address, &layout.balance_slot);
+
+    let balance_response =
+        client.get_proof(&input.ctoken_address, block_number, &encode(slot.to_vec()))?;
+    let storage_proof = |slot: &[u8; 32]| -> Result<Vec<Vec<u8>>> {
+        Ok(client
+            .get_proof(&input.ctoken_address, block_number, &encode(slot.to_vec()))?
+            .storage_proof)
+    };
+
+    let cash = match &input.underlying {
+        None => CashProof::Native,
+        Some(underlying) => {
+            // the cToken is the holder of the underlying token
+            let balance_slot = decode_hex_string(&underlying.balance_slot);
+            let slot = mapping_slot(underlying.layout, &ctoken_address, &balance_slot);
+            let proof_response = client.get_proof(
+                &underlying.contract_address,
+                block_number,
+                &encode(slot.to_vec()),
+            )?;
+            CashProof::Token {
+                underlying_proof: storage_proof(&layout.underlying_slot)?,
+                underlying_address: decode_hex_string(&underlying.contract_address),
+                balance_slot,
+                layout: underlying.layout,
+                account_proof: proof_response.account_proof,
+                storage_proof: proof_response.storage_proof,
+            }
+        }
+    };
+
+    let result = CTokenProofInput {
+        ctoken_address,
+        ctoken_layout: layout.clone(),
+        user_address,
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        account_proof: balance_response.account_proof,
+        balance_proof: balance_response.storage_proof,
+        total_borrows_proof: storage_proof(&layout.total_borrows_slot)?,
+        total_reserves_proof: storage_proof(&layout.total_reserves_slot)?,
+        total_supply_proof: storage_proof(&layout.total_supply_slot)?,
+        initial_exchange_rate_proof: storage_proof(&layout.initial_exchange_rate_slot)?,
+        cash,
+        threshold: input.threshold,
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_votes_input(input: &VotesRequest) -> Result<VotesProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    // both proofs are requested against the same block, hence the same storage root
+    let block_number = &block_response.number;
+    let user_address: [u8; 20] = decode_hex_string(&input.user_address);
+    let checkpoints_slot = decode_hex_string(&input.checkpoints_slot);
+    let array_slot = mapping_slot(SlotLayout::Solidity, &user_address, &checkpoints_slot);
+
+    // the array length locates its last element
+    let length_response = client.get_proof(
+        &input.token_address,
+        block_number,
+        &encode(array_slot.to_vec()),
+    )?;
+    let length = packed_field(
+        &client.get_storage_at(
+            &input.token_address,
+            &encode(array_slot.to_vec()),
+            block_number,
+        )?,
+        0,
+        32,
+    )
+    .as_u64();
+    let checkpoint_proof = match length {
+        0 => vec![],
+        length => {
+            client
+                .get_proof(
+                    &input.token_address,
+                    block_number,
+                    &encode(array_element_slot(&array_slot, length - 1).to_vec()),
+                )?
+                .storage_proof
+        }
+    };
+
+    let result = VotesProofInput {
+        token_address: decode_hex_string(&input.token_address),
+        checkpoints_slot,
+        key_bytes: input.key_bytes,
+        user_address,
+        root: block_response.storage_hash,
+        block_hash: block_response.block_hash,
+        account_proof: length_response.account_proof,
+        length_proof: length_response.storage_proof,
+        checkpoint_proof,
+        threshold: input.threshold,
+        signature: decode_hex_string(&input.signature),
+        message: input.message.as_bytes().to_vec(),
+    };
+
+    Ok(result)
+}
+
+pub fn get_vote_input(input: &VoteRequest) -> Result<VoteProofInput> {
+    let client = EthereumRpcClient::new(&input.provider);
+    let block_response = client.get_block_by_number(&input.block_number)?;
+    let asset = get_asset_proof(
+        &client,
+        &input.token,
+        &input.user_address,
+        &block_response.number,
+    )?;
+
+    let witness = match &input.ownership {
+        VoteOwnership::PrivateKey(private_key) => VoteWitness::PrivateKey(*private_key),
+        VoteOwnership::Signature { signature, message } => VoteWitness::Signature {
+            user_address: decode_hex_string(&input.user_address),
+            signature: decode_hex_string(signature),
+            message: message.as_bytes().to_vec(),
+        },
+    };
+
+    let result = VoteProofInput {
+        witness,
+        header: block_response.header_rlp,
+        block_hash: block_response.block_hash,
+        asset,
+        poll_id: input.poll_id,
+        choice: input.choice,
+        tiers: input.tiers.clone(),
+    };
+
+    Ok(result)
+}
+
+// Proof of the user's native balance, or of their balance of the given token
+fn get_asset_proof(
+    client: &EthereumRpcClient,
+    token: &Option<TokenAsset>,
+    user_address: &str,
+    block_number: &str,
+) -> Result<AssetProof> {
+    let asset = match token {
+        None => AssetProof::Native {
+            account_proof: client
+                .get_proof(user_address, block_number, "")?
+                .account_proof,
+        },
+        Some(token) => {
+            let balance_slot = decode_hex_string(&token.balance_slot);
+            let slot = mapping_slot(
+                token.layout,
+                &decode_hex_string(user_address),
+                &balance_slot,
+            );
+            let proof_response = client.get_proof(
+                &token.contract_address,
+                block_number,
+                &encode(slot.to_vec()),
+            )?;
+            AssetProof::Token {
+                contract_address: decode_hex_string(&token.contract_address),
+                balance_slot,
+                layout: token.layout,
+                account_proof: proof_response.account_proof,
+                storage_proof: proof_response.storage_proof,
+            }
+        }
+    };
+
+    Ok(asset)
+}
+
 // Helper function for decoding hex strings to Vec<u8>
 fn decode_hex_string<T: FromHexPrefixed>(hex: &str) -> T {
     decode(hex).expect("Failed to decode")
@@ -149,11 +851,64 @@ fn parse_block_response(block_response: &Map<String, Value>) -> Result<EthGetBlo
         number: block_response["number"].as_str().unwrap().to_owned(),
         storage_hash: decode_hex_string(block_response["stateRoot"].as_str().unwrap()),
         block_hash: decode_hex_string(block_response["hash"].as_str().unwrap()),
+        header_rlp: encode_block_header(block_response)?,
     };
 
     Ok(block_info)
 }
 
+// Header fields in RLP order, with whether they are quantities (minimally encoded integers).
+// Fields after the first 15 were added by later forks and are only present from then on.
+const HEADER_FIELDS: [(&str, bool); 21] = [
+    ("parentHash", false),
+    ("sha3Uncles", false),
+    ("miner", false),
+    ("stateRoot", false),
+    ("transactionsRoot", false),
+    ("receiptsRoot", false),
+    ("logsBloom", false),
+    ("difficulty", true),
+    ("number", true),
+    ("gasLimit", true),
+    ("gasUsed", true),
+    ("timestamp", true),
+    ("extraData", false),
+    ("mixHash", false),
+    ("nonce", false),
+    ("baseFeePerGas", true),
+    ("withdrawalsRoot", false),
+    ("blobGasUsed", true),
+    ("excessBlobGas", true),
+    ("parentBeaconBlockRoot", false),
+    ("requestsHash", false),
+];
+
+// Rebuilds the RLP-encoded header from the JSON fields of eth_getBlockByNumber
+fn encode_block_header(block_response: &Map<String, Value>) -> Result<Vec<u8>> {
+    let mut fields = Vec::with_capacity(HEADER_FIELDS.len());
+    for (index, (name, is_quantity)) in HEADER_FIELDS.iter().enumerate() {
+        let value = match block_response.get(*name).and_then(Value::as_str) {
+            Some(value) => value.trim_start_matches("0x"),
+            None if index >= 15 => break,
+            None => return Err(format!("Block header is missing {}", name).into()),
+        };
+        let value = if *is_quantity {
+            // quantities are encoded without leading zeros, and zero as the empty string
+            let value = value.trim_start_matches('0');
+            hex::decode(format!("{}{}", "0".repeat(value.len() % 2), value))?
+        } else {
+            hex::decode(value)?
+        };
+        fields.push(value);
+    }
+
+    let mut stream = rlp::RlpStream::new_list(fields.len());
+    for field in &fields {
+        stream.append(field);
+    }
+    Ok(stream.out().to_vec())
+}
+
 // Function to parse the JSON response of eth_getProof into EthGetProofBody
 fn parse_proof_response(proof_response: &Map<String, Value>) -> Result<EthGetProofBody> {
     // Parse accountProof field to Vec<Vec<u8>>
@@ -201,6 +956,40 @@ mod tests {
         assert_eq!(result, expected_result);
     }
 
+    #[test]
+    fn test_encode_block_header() {
+        let mut block = serde_json::json!({
+            "parentHash": format!("0x{}", "11".repeat(32)),
+            "sha3Uncles": format!("0x{}", "22".repeat(32)),
+            "miner": format!("0x{}", "33".repeat(20)),
+            "stateRoot": format!("0x{}", "44".repeat(32)),
+            "transactionsRoot": format!("0x{}", "55".repeat(32)),
+            "receiptsRoot": format!("0x{}", "66".repeat(32)),
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "difficulty": "0x0",
+            "number": "0x1036640",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "timestamp": "0x6430d2f7",
+            "extraData": "0x",
+            "mixHash": format!("0x{}", "77".repeat(32)),
+            "nonce": "0x0000000000000000",
+            "baseFeePerGas": "0x7",
+        });
+        let header = encode_block_header(block.as_object().unwrap()).unwrap();
+        let decoded: Vec<Vec<u8>> = rlp::Rlp::new(&header).as_list().unwrap();
+
+        assert_eq!(decoded.len(), 16);
+        assert_eq!(decoded[3], vec![0x44; 32]);
+        assert_eq!(decoded[7], Vec::<u8>::new());
+        assert_eq!(decoded[8], vec![0x01, 0x03, 0x66, 0x40]);
+        assert_eq!(decoded[14], vec![0; 8]);
+        assert_eq!(decoded[15], vec![0x07]);
+
+        block.as_object_mut().unwrap().remove("stateRoot");
+        assert!(encode_block_header(block.as_object().unwrap()).is_err());
+    }
+
     #[test]
     fn test_build_request_payload() {
         let method = "eth_getBlockByNumber";
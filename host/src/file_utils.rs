@@ -1,8 +1,24 @@
-use crate::ethereum::requests::{ContractRequest, NativeRequest};
-use proof_core::eth_utils::format_eth_message;
+use crate::address_tree::read_address_tree;
+use crate::ethereum::requests::{
+    AaveRequest, AggregateRequest, CTokenRequest, ContractRequest, InactivityRequest, LpRequest,
+    NativeKeyRequest, NativeRequest, OwnerRequest, SignedAccount, StethRequest, SupplyShareRequest,
+    TokenAsset, UsdRequest, VaultAssetsSource, VaultRequest, VoteOwnership, VoteRequest,
+    VotesRequest,
+};
+use crate::keystore::load_keystore;
+use crate::token_registry::{TokenInfo, TokenRegistry};
+use primitive_types::U256;
+use proof_core::{
+    eth_utils::{format_eth_message, poll_id, private_key_to_address, vote_message, SlotLayout},
+    proof_io::{
+        AccountField, AggregatorLayout, BalanceClaim, BlacklistLayout, CTokenLayout, LidoLayout,
+        MerkleProof, NonMembershipProof, PairLayout, Predicate,
+    },
+};
 use risc0_zkvm::Receipt;
 use serde::{de::Error, Serialize};
 use serde_json::{Error as SerdeJsonError, Value};
+use sha3::{Digest, Keccak256};
 
 use std::fs::{create_dir_all, File};
 use std::io::{Read, Write};
@@ -34,7 +50,15 @@ pub fn parse_json_native(filename: &str) -> Result<NativeRequest, SerdeJsonError
     let block_number = String::from(data["block_number"].as_str().unwrap());
     let signature = String::from(data["signature"].as_str().unwrap());
     let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
-    let expected_balance = data["expected_balance"].as_u64().unwrap();
+    let balance_claim = parse_balance_claim(&data)?;
+    let address_salt = data["address_salt"].as_str().map(String::from);
+    let balance_blinding = data["balance_blinding"].as_str().map(String::from);
+    let predicate = match &data["predicate"] {
+        Value::Null => None,
+        predicate => Some(parse_predicate(predicate)?),
+    };
+    let address_allowlist = parse_address_allowlist(&data, &user_address)?;
+    let sanctions = parse_sanctions(&data, &user_address)?;
 
     Ok(NativeRequest {
         provider,
@@ -42,10 +66,137 @@ pub fn parse_json_native(filename: &str) -> Result<NativeRequest, SerdeJsonError
         block_number,
         signature,
         message,
+        balance_claim,
+        address_salt,
+        balance_blinding,
+        predicate,
+        address_allowlist,
+        sanctions,
+    })
+}
+
+// Proof that the user's address is not in the "sanctions_list" sorted tree file, if one is given
+fn parse_sanctions(
+    data: &Value,
+    user_address: &str,
+) -> Result<Option<NonMembershipProof>, SerdeJsonError> {
+    match data["sanctions_list"].as_str() {
+        Some(filename) => read_address_tree(filename)
+            .and_then(|tree| tree.non_membership_proof(user_address))
+            .map(Some)
+            .map_err(SerdeJsonError::custom),
+        None => Ok(None),
+    }
+}
+
+// Inclusion proof of the user's address in the "address_allowlist" tree file, if one is given
+fn parse_address_allowlist(
+    data: &Value,
+    user_address: &str,
+) -> Result<Option<MerkleProof>, SerdeJsonError> {
+    match data["address_allowlist"].as_str() {
+        Some(filename) => read_address_tree(filename)
+            .and_then(|tree| tree.membership_proof(user_address))
+            .map(Some)
+            .map_err(SerdeJsonError::custom),
+        None => Ok(None),
+    }
+}
+
+pub fn parse_json_native_key(
+    filename: &str,
+    passphrase: &str,
+) -> Result<NativeKeyRequest, Box<dyn std::error::Error>> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let context = String::from(data["context"].as_str().unwrap());
+    let balance_claim = parse_balance_claim(&data)?;
+
+    // The private key is never read from plain JSON, only from an encrypted keystore
+    let private_key = load_keystore(data["keystore"].as_str().unwrap(), passphrase)?;
+    let user_address = prefix_hex::encode(private_key_to_address(&private_key)?.to_vec());
+
+    Ok(NativeKeyRequest {
+        provider,
+        user_address,
+        block_number,
+        private_key,
+        context,
+        balance_claim,
+    })
+}
+
+pub fn parse_json_aggregate(filename: &str) -> Result<AggregateRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+    let balance_claim = parse_balance_claim(&data)?;
+    let accounts: Vec<SignedAccount> = serde_json::from_value(data["accounts"].clone())?;
+
+    Ok(AggregateRequest {
+        provider,
+        block_number,
+        message,
+        balance_claim,
+        accounts,
+    })
+}
+
+pub fn parse_json_inactivity(filename: &str) -> Result<InactivityRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let start_block = String::from(data["start_block"].as_str().unwrap());
+    let end_block = String::from(data["end_block"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+    let expected_balance = parse_amount(&data["expected_balance"])?;
+
+    Ok(InactivityRequest {
+        provider,
+        user_address,
+        start_block,
+        end_block,
+        signature,
+        message,
         expected_balance,
     })
 }
 
+// bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)
+pub const EIP1967_ADMIN_SLOT: &str =
+    "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103";
+
+pub fn parse_json_owner(filename: &str) -> Result<OwnerRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+    let contract_address = String::from(data["contract_address"].as_str().unwrap());
+    let owner_slot = match data["owner_slot"].as_str().unwrap() {
+        "eip1967_admin" => String::from(EIP1967_ADMIN_SLOT),
+        slot => String::from(slot),
+    };
+
+    Ok(OwnerRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
+        contract_address,
+        owner_slot,
+    })
+}
+
 pub fn parse_json_contract(filename: &str) -> Result<ContractRequest, SerdeJsonError> {
     let data = read_json_file(filename)?;
 
@@ -54,10 +205,46 @@ pub fn parse_json_contract(filename: &str) -> Result<ContractRequest, SerdeJsonE
     let block_number = String::from(data["block_number"].as_str().unwrap());
     let signature = String::from(data["signature"].as_str().unwrap());
     let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
-    let expected_balance = data["expected_balance"].as_u64().unwrap();
 
-    let contract_address = String::from(data["contract_address"].as_str().unwrap());
-    let balance_slot = String::from(data["balance_slot"].as_str().unwrap());
+    // A registry token replaces the contract address, balance slot and layout, and amounts are
+    // then given in the token's human units
+    let registry_token = parse_token(&data)?;
+    let (contract_address, balance_slot, layout, balance_claim) = match &registry_token {
+        Some(token) => {
+            let balance_claim =
+                parse_balance_claim_with(&data, &|value| parse_token_amount(value, token))?;
+            (
+                token.address.clone(),
+                token.balance_slot_hex(),
+                token.layout,
+                balance_claim,
+            )
+        }
+        None => (
+            String::from(data["contract_address"].as_str().unwrap()),
+            String::from(data["balance_slot"].as_str().unwrap()),
+            serde_json::from_value(data["layout"].clone()).unwrap_or(SlotLayout::Solidity),
+            parse_balance_claim(&data)?,
+        ),
+    };
+    let address_salt = data["address_salt"].as_str().map(String::from);
+    let balance_blinding = data["balance_blinding"].as_str().map(String::from);
+
+    // Frozen accounts are read where the registry token records them, otherwise at a
+    // "blacklist_slot" mapping or in the balance's highest bit with "blacklist_in_balance"
+    let blacklist = if let Some(token) = registry_token {
+        token.blacklist_layout()
+    } else if !data["blacklist_slot"].is_null() {
+        Some(BlacklistLayout::Mapping(parse_slot(
+            &data["blacklist_slot"],
+        )?))
+    } else if data["blacklist_in_balance"].as_bool().unwrap_or(false) {
+        Some(BlacklistLayout::BalanceHighBit)
+    } else {
+        None
+    };
+    let address_allowlist = parse_address_allowlist(&data, &user_address)?;
+    let sanctions = parse_sanctions(&data, &user_address)?;
 
     Ok(ContractRequest {
         provider,
@@ -65,12 +252,776 @@ pub fn parse_json_contract(filename: &str) -> Result<ContractRequest, SerdeJsonE
         block_number,
         signature,
         message,
-        expected_balance,
+        balance_claim,
+        contract_address,
+        balance_slot,
+        layout,
+        address_salt,
+        balance_blinding,
+        blacklist,
+        address_allowlist,
+        sanctions,
+    })
+}
+
+// Maximum age of a price unless "max_age" is given, the one hour heartbeat of the ETH / USD feed
+const DEFAULT_MAX_PRICE_AGE: u64 = 3600;
+
+pub fn parse_json_usd(filename: &str) -> Result<UsdRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+
+    // A registry token is valued instead of the native asset (18 decimals)
+    let (token, asset_decimals) = match parse_token(&data)? {
+        Some(token) => {
+            let asset = TokenAsset {
+                contract_address: token.address.clone(),
+                balance_slot: token.balance_slot_hex(),
+                layout: token.layout,
+            };
+            (Some(asset), token.decimals)
+        }
+        None => (None, 18),
+    };
+
+    let aggregator_address = String::from(data["aggregator_address"].as_str().unwrap());
+    let aggregator_layout = AggregatorLayout {
+        hot_vars_slot: parse_slot(&data["hot_vars_slot"])?,
+        round_id_offset: data["round_id_offset"].as_u64().unwrap() as u8,
+        transmissions_slot: parse_slot(&data["transmissions_slot"])?,
+    };
+    let feed_decimals = data["feed_decimals"].as_u64().unwrap() as u8;
+    let max_age = data["max_age"].as_u64().unwrap_or(DEFAULT_MAX_PRICE_AGE);
+    let usd_threshold = data["usd_threshold"].as_u64().unwrap();
+
+    Ok(UsdRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
+        token,
+        asset_decimals,
+        aggregator_address,
+        aggregator_layout,
+        feed_decimals,
+        max_age,
+        usd_threshold,
+    })
+}
+
+// Looks up the request's "token" symbol on its "chain_id" (1 by default), in the registry file given
+// by "token_registry" or in the bundled one
+pub fn parse_token(data: &Value) -> Result<Option<TokenInfo>, SerdeJsonError> {
+    let symbol = match data["token"].as_str() {
+        Some(symbol) => symbol,
+        None => return Ok(None),
+    };
+    let registry =
+        TokenRegistry::load(data["token_registry"].as_str()).map_err(SerdeJsonError::custom)?;
+    let chain_id = data["chain_id"].as_u64().unwrap_or(1);
+    let token = registry
+        .get(chain_id, symbol)
+        .map_err(SerdeJsonError::custom)?;
+
+    Ok(Some(token.clone()))
+}
+
+pub fn parse_json_supply_share(filename: &str) -> Result<SupplyShareRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+
+    let (contract_address, balance_slot, layout, total_supply_slot) = match parse_token(&data)? {
+        Some(token) => (
+            token.address.clone(),
+            token.balance_slot_hex(),
+            token.layout,
+            token
+                .total_supply_slot_hex()
+                .ok_or(SerdeJsonError::custom("Token has no total supply slot"))?,
+        ),
+        None => (
+            String::from(data["contract_address"].as_str().unwrap()),
+            String::from(data["balance_slot"].as_str().unwrap()),
+            serde_json::from_value(data["layout"].clone()).unwrap_or(SlotLayout::Solidity),
+            String::from(data["total_supply_slot"].as_str().unwrap()),
+        ),
+    };
+    let bps = data["bps"]
+        .as_u64()
+        .filter(|bps| *bps <= 10000)
+        .ok_or(SerdeJsonError::custom("bps must be between 0 and 10000"))? as u16;
+
+    Ok(SupplyShareRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
         contract_address,
         balance_slot,
+        layout,
+        total_supply_slot,
+        bps,
     })
 }
 
+// Total assets are summed from the vault's "total_assets_slots" if given, otherwise taken as the
+// vault's balance of the "underlying_token" registry entry or of the "underlying_address" token
+pub fn parse_json_vault(filename: &str) -> Result<VaultRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+
+    let vault_address = String::from(data["vault_address"].as_str().unwrap());
+    let balance_slot = String::from(data["balance_slot"].as_str().unwrap());
+    let layout = serde_json::from_value(data["layout"].clone()).unwrap_or(SlotLayout::Solidity);
+    let total_supply_slot = String::from(data["total_supply_slot"].as_str().unwrap());
+    let decimals_offset = data["decimals_offset"]
+        .as_u64()
+        .map(u8::try_from)
+        .unwrap_or(Ok(0))
+        .map_err(SerdeJsonError::custom)?;
+
+    let underlying = match data["underlying_token"].as_str() {
+        Some(symbol) => parse_token(&serde_json::json!({
+            "token": symbol,
+            "token_registry": data["token_registry"],
+            "chain_id": data["chain_id"],
+        }))?,
+        None => None,
+    };
+    let (assets, threshold) = match (data["total_assets_slots"].as_array(), &underlying) {
+        (Some(slots), _) => {
+            let slots = slots
+                .iter()
+                .map(|slot| parse_slot(slot).map(|slot| prefix_hex::encode(slot.to_vec())))
+                .collect::<Result<_, _>>()?;
+            let threshold = match &underlying {
+                Some(token) => parse_token_amount(&data["threshold"], token)?,
+                None => parse_amount(&data["threshold"])?,
+            };
+            (VaultAssetsSource::Stored(slots), threshold)
+        }
+        (None, Some(token)) => (
+            VaultAssetsSource::Held(TokenAsset {
+                contract_address: token.address.clone(),
+                balance_slot: token.balance_slot_hex(),
+                layout: token.layout,
+            }),
+            parse_token_amount(&data["threshold"], token)?,
+        ),
+        (None, None) => (
+            VaultAssetsSource::Held(TokenAsset {
+                contract_address: String::from(data["underlying_address"].as_str().unwrap()),
+                balance_slot: String::from(data["underlying_balance_slot"].as_str().unwrap()),
+                layout: serde_json::from_value(data["underlying_layout"].clone())
+                    .unwrap_or(SlotLayout::Solidity),
+            }),
+            parse_amount(&data["threshold"])?,
+        ),
+    };
+
+    Ok(VaultRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
+        vault_address,
+        balance_slot,
+        layout,
+        total_supply_slot,
+        assets,
+        decimals_offset,
+        threshold,
+    })
+}
+
+// Lido stETH and wstETH on mainnet
+pub const STETH_ADDRESS: &str = "0xae7ab96520de3a18e5e111b5eaab095312d7fe84";
+pub const WSTETH_ADDRESS: &str = "0x7f39c581f595b53c5cb19bd0b3f8da6c935e2ca0";
+
+// Unstructured storage position named by the hash of its name
+fn lido_position(data: &Value, key: &str, name: &str) -> Result<[u8; 32], SerdeJsonError> {
+    match &data[key] {
+        Value::Null => Ok(Keccak256::digest(name.as_bytes()).into()),
+        slot => parse_slot(slot),
+    }
+}
+
+// Every Lido slot or position defaults to the mainnet layout, and can be overridden by its own field
+pub fn parse_lido_layout(data: &Value) -> Result<LidoLayout, SerdeJsonError> {
+    Ok(LidoLayout {
+        shares_slot: match &data["shares_slot"] {
+            Value::Null => [0_u8; 32],
+            slot => parse_slot(slot)?,
+        },
+        total_shares_position: lido_position(
+            data,
+            "total_shares_position",
+            "lido.StETH.totalShares",
+        )?,
+        buffered_ether_position: lido_position(
+            data,
+            "buffered_ether_position",
+            "lido.Lido.bufferedEther",
+        )?,
+        cl_balance_position: lido_position(data, "cl_balance_position", "lido.Lido.beaconBalance")?,
+        deposited_validators_position: lido_position(
+            data,
+            "deposited_validators_position",
+            "lido.Lido.depositedValidators",
+        )?,
+        cl_validators_position: lido_position(
+            data,
+            "cl_validators_position",
+            "lido.Lido.beaconValidators",
+        )?,
+    })
+}
+
+// With "wrapped" set, the wstETH balance ("wsteth_address", mainnet by default, whose balances sit at
+// "wsteth_balance_slot", 0 by default) is counted along with the stETH shares
+pub fn parse_json_steth(filename: &str) -> Result<StethRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+
+    let steth_address = String::from(data["steth_address"].as_str().unwrap_or(STETH_ADDRESS));
+    let lido_layout = parse_lido_layout(&data)?;
+    let wrapped = match data["wrapped"].as_bool().unwrap_or(false) {
+        true => Some(TokenAsset {
+            contract_address: String::from(
+                data["wsteth_address"].as_str().unwrap_or(WSTETH_ADDRESS),
+            ),
+            balance_slot: prefix_hex::encode(
+                match &data["wsteth_balance_slot"] {
+                    Value::Null => [0_u8; 32],
+                    slot => parse_slot(slot)?,
+                }
+                .to_vec(),
+            ),
+            layout: SlotLayout::Solidity,
+        }),
+        false => None,
+    };
+    let threshold = parse_amount(&data["threshold"])?;
+
+    Ok(StethRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
+        steth_address,
+        lido_layout,
+        wrapped,
+        threshold,
+    })
+}
+
+// Aave v3 Pool on mainnet. Both the Pool's reserves and the aToken's user states sit at slot 52,
+// after the 52 slots of VersionedInitializable
+pub const AAVE_V3_POOL_ADDRESS: &str = "0x87870bca3f3fd6335c3f4ce8392d69350b4fa4e2";
+const AAVE_V3_STORAGE_SLOT: u64 = 52;
+
+// The reserve's "asset" is a registry symbol, in which case "threshold" is in human units, or an
+// "asset_address". The Pool and slots default to mainnet Aave v3.
+pub fn parse_json_aave(filename: &str) -> Result<AaveRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+
+    let pool_address = String::from(
+        data["pool_address"]
+            .as_str()
+            .unwrap_or(AAVE_V3_POOL_ADDRESS),
+    );
+    let slot = |key: &str| -> Result<String, SerdeJsonError> {
+        let slot = match &data[key] {
+            Value::Null => parse_slot(&Value::from(AAVE_V3_STORAGE_SLOT))?,
+            slot => parse_slot(slot)?,
+        };
+        Ok(prefix_hex::encode(slot.to_vec()))
+    };
+    let reserves_slot = slot("reserves_slot")?;
+    let user_state_slot = slot("user_state_slot")?;
+
+    let asset = match data["asset"].as_str() {
+        Some(symbol) => parse_token(&serde_json::json!({
+            "token": symbol,
+            "token_registry": data["token_registry"],
+            "chain_id": data["chain_id"],
+        }))?,
+        None => None,
+    };
+    let (asset_address, threshold) = match &asset {
+        Some(token) => (
+            token.address.clone(),
+            parse_token_amount(&data["threshold"], token)?,
+        ),
+        None => (
+            String::from(data["asset_address"].as_str().unwrap()),
+            parse_amount(&data["threshold"])?,
+        ),
+    };
+    let a_token_address = data["a_token_address"].as_str().map(String::from);
+
+    Ok(AaveRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
+        pool_address,
+        reserves_slot,
+        asset_address,
+        a_token_address,
+        user_state_slot,
+        threshold,
+    })
+}
+
+// Slots of a Uniswap v2 pair: totalSupply, balanceOf, token0, token1 and the packed reserves. Forks
+// with another layout override them by the field of the same name.
+const UNISWAP_V2_PAIR_SLOTS: [(&str, u64); 5] = [
+    ("total_supply_slot", 0),
+    ("balance_slot", 1),
+    ("token0_slot", 6),
+    ("token1_slot", 7),
+    ("reserves_slot", 8),
+];
+
+pub fn parse_json_lp(filename: &str) -> Result<LpRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+    let pair_address = String::from(data["pair_address"].as_str().unwrap());
+
+    let mut slots = [[0_u8; 32]; 5];
+    for (slot, (key, default)) in slots.iter_mut().zip(UNISWAP_V2_PAIR_SLOTS) {
+        *slot = match &data[key] {
+            Value::Null => parse_slot(&Value::from(default))?,
+            value => parse_slot(value)?,
+        };
+    }
+    let [total_supply_slot, balance_slot, token0_slot, token1_slot, reserves_slot] = slots;
+    let pair_layout = PairLayout {
+        total_supply_slot,
+        balance_slot,
+        token0_slot,
+        token1_slot,
+        reserves_slot,
+    };
+
+    let threshold0 = parse_amount(&data["threshold0"])?;
+    let threshold1 = parse_amount(&data["threshold1"])?;
+
+    Ok(LpRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
+        pair_address,
+        pair_layout,
+        threshold0,
+        threshold1,
+    })
+}
+
+// Slots of a Compound v2 cToken: totalBorrows, totalReserves, totalSupply, accountTokens,
+// underlying and initialExchangeRateMantissa. Deployments with another layout override them by the
+// field of the same name.
+const COMPOUND_V2_CTOKEN_SLOTS: [(&str, u64); 6] = [
+    ("total_borrows_slot", 11),
+    ("total_reserves_slot", 12),
+    ("total_supply_slot", 13),
+    ("balance_slot", 14),
+    ("underlying_slot", 17),
+    ("initial_exchange_rate_slot", 7),
+];
+
+// The underlying is the "underlying_token" registry entry, in which case "threshold" is in human
+// units, or the "underlying_address" token. Without either, the cToken is cETH.
+pub fn parse_json_ctoken(filename: &str) -> Result<CTokenRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+    let ctoken_address = String::from(data["ctoken_address"].as_str().unwrap());
+
+    let mut slots = [[0_u8; 32]; 6];
+    for (slot, (key, default)) in slots.iter_mut().zip(COMPOUND_V2_CTOKEN_SLOTS) {
+        *slot = match &data[key] {
+            Value::Null => parse_slot(&Value::from(default))?,
+            value => parse_slot(value)?,
+        };
+    }
+    let [total_borrows_slot, total_reserves_slot, total_supply_slot, balance_slot, underlying_slot, initial_exchange_rate_slot] =
+        slots;
+    let ctoken_layout = CTokenLayout {
+        total_borrows_slot,
+        total_reserves_slot,
+        total_supply_slot,
+        balance_slot,
+        underlying_slot,
+        initial_exchange_rate_slot,
+    };
+
+    let underlying_token = match data["underlying_token"].as_str() {
+        Some(symbol) => parse_token(&serde_json::json!({
+            "token": symbol,
+            "token_registry": data["token_registry"],
+            "chain_id": data["chain_id"],
+        }))?,
+        None => None,
+    };
+    let (underlying, threshold) = match (&underlying_token, data["underlying_address"].as_str()) {
+        (Some(token), _) => (
+            Some(TokenAsset {
+                contract_address: token.address.clone(),
+                balance_slot: token.balance_slot_hex(),
+                layout: token.layout,
+            }),
+            parse_token_amount(&data["threshold"], token)?,
+        ),
+        (None, Some(underlying_address)) => (
+            Some(TokenAsset {
+                contract_address: String::from(underlying_address),
+                balance_slot: String::from(data["underlying_balance_slot"].as_str().unwrap()),
+                layout: serde_json::from_value(data["underlying_layout"].clone())
+                    .unwrap_or(SlotLayout::Solidity),
+            }),
+            parse_amount(&data["threshold"])?,
+        ),
+        (None, None) => (None, parse_amount(&data["threshold"])?),
+    };
+
+    Ok(CTokenRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
+        ctoken_address,
+        ctoken_layout,
+        underlying,
+        threshold,
+    })
+}
+
+// The token is a "token" registry entry, in which case "threshold" is in human units, or a
+// "token_address". Checkpoint keys are uint32 block numbers unless "checkpoint_key_bytes" says
+// otherwise, e.g. 6 for the uint48 timepoints of OpenZeppelin 5.
+pub fn parse_json_votes(filename: &str) -> Result<VotesRequest, SerdeJsonError> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let user_address = String::from(data["user_address"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let signature = String::from(data["signature"].as_str().unwrap());
+    let message = format_eth_message(String::from(data["message"].as_str().unwrap()));
+
+    let (token_address, threshold) = match parse_token(&data)? {
+        Some(token) => (
+            token.address.clone(),
+            parse_token_amount(&data["threshold"], &token)?,
+        ),
+        None => (
+            String::from(data["token_address"].as_str().unwrap()),
+            parse_amount(&data["threshold"])?,
+        ),
+    };
+    let checkpoints_slot = prefix_hex::encode(parse_slot(&data["checkpoints_slot"])?.to_vec());
+    let key_bytes = data["checkpoint_key_bytes"]
+        .as_u64()
+        .unwrap_or(4)
+        .try_into()
+        .map_err(SerdeJsonError::custom)?;
+
+    Ok(VotesRequest {
+        provider,
+        user_address,
+        block_number,
+        signature,
+        message,
+        token_address,
+        checkpoints_slot,
+        key_bytes,
+        threshold,
+    })
+}
+
+// Text signed by a voter, the vote message of the request's "poll" and "choice" if it has a poll
+pub fn vote_text(data: &Value) -> Result<Option<String>, SerdeJsonError> {
+    let poll = match data["poll"].as_str() {
+        Some(poll) => poll,
+        None => return Ok(None),
+    };
+    let choice = data["choice"]
+        .as_u64()
+        .and_then(|choice| u32::try_from(choice).ok())
+        .ok_or(SerdeJsonError::custom("choice must be a 32-bit number"))?;
+
+    Ok(Some(vote_message(&poll_id(poll), choice)))
+}
+
+// The vote is weighted by the balance of the "token" registry entry (tiers in human units), of the
+// "contract_address" token, or of the native asset, at the poll's snapshot "block_number". Public
+// "tiers" weight it by the highest tier reached rather than by the exact balance. The voter proves
+// ownership with the "keystore" of their key, whose passphrase is only asked for then, or with the
+// "signature" of the vote message by "user_address".
+pub fn parse_json_vote(
+    filename: &str,
+    passphrase: impl FnOnce() -> Result<String, Box<dyn std::error::Error>>,
+) -> Result<VoteRequest, Box<dyn std::error::Error>> {
+    let data = read_json_file(filename)?;
+
+    let provider = String::from(data["provider"].as_str().unwrap());
+    let block_number = String::from(data["block_number"].as_str().unwrap());
+    let poll_id = poll_id(
+        data["poll"]
+            .as_str()
+            .ok_or(SerdeJsonError::missing_field("poll"))?,
+    );
+    let choice = data["choice"]
+        .as_u64()
+        .and_then(|choice| u32::try_from(choice).ok())
+        .ok_or(SerdeJsonError::custom("choice must be a 32-bit number"))?;
+
+    let registry_token = parse_token(&data)?;
+    let token = match (&registry_token, data["contract_address"].as_str()) {
+        (Some(token), _) => Some(TokenAsset {
+            contract_address: token.address.clone(),
+            balance_slot: token.balance_slot_hex(),
+            layout: token.layout,
+        }),
+        (None, Some(contract_address)) => Some(TokenAsset {
+            contract_address: String::from(contract_address),
+            balance_slot: String::from(data["balance_slot"].as_str().unwrap()),
+            layout: serde_json::from_value(data["layout"].clone()).unwrap_or(SlotLayout::Solidity),
+        }),
+        (None, None) => None,
+    };
+    let tiers = match data["tiers"].as_array() {
+        Some(tiers) => Some(
+            tiers
+                .iter()
+                .map(|tier| match &registry_token {
+                    Some(token) => parse_token_amount(tier, token),
+                    None => parse_amount(tier),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        None => None,
+    };
+
+    // The private key is never read from plain JSON, only from an encrypted keystore
+    let (user_address, ownership) = match data["keystore"].as_str() {
+        Some(keystore) => {
+            let private_key = load_keystore(keystore, &passphrase()?)?;
+            let user_address = prefix_hex::encode(private_key_to_address(&private_key)?.to_vec());
+            (user_address, VoteOwnership::PrivateKey(private_key))
+        }
+        None => {
+            let user_address = String::from(data["user_address"].as_str().unwrap());
+            let signature = String::from(data["signature"].as_str().unwrap());
+            let message =
+                format_eth_message(vote_text(&data)?.ok_or(SerdeJsonError::missing_field("poll"))?);
+            (
+                user_address,
+                VoteOwnership::Signature { signature, message },
+            )
+        }
+    };
+
+    Ok(VoteRequest {
+        provider,
+        user_address,
+        block_number,
+        ownership,
+        token,
+        poll_id,
+        choice,
+        tiers,
+    })
+}
+
+// Parses a storage slot, given either as a number or as a 0x-prefixed 32-byte word
+pub fn parse_slot(value: &Value) -> Result<[u8; 32], SerdeJsonError> {
+    match value {
+        Value::Number(number) => {
+            let slot = number
+                .as_u64()
+                .ok_or(SerdeJsonError::custom("Invalid slot"))?;
+            let mut slot_bytes = [0_u8; 32];
+            slot_bytes[24..].copy_from_slice(&slot.to_be_bytes());
+            Ok(slot_bytes)
+        }
+        Value::String(slot) => {
+            prefix_hex::decode(slot.as_str()).map_err(|_| SerdeJsonError::custom("Invalid slot"))
+        }
+        _ => Err(SerdeJsonError::custom("Invalid slot")),
+    }
+}
+
+// A public "tiers" table takes precedence over an inclusive "balance_range", which takes precedence
+// over a caller-chosen "expected_balance" compared according to "comparison" (geq by default)
+pub fn parse_balance_claim(data: &Value) -> Result<BalanceClaim, SerdeJsonError> {
+    parse_balance_claim_with(data, &parse_amount)
+}
+
+// Same as parse_balance_claim, with amounts parsed by the given function
+fn parse_balance_claim_with(
+    data: &Value,
+    parse_amount: &dyn Fn(&Value) -> Result<[u8; 32], SerdeJsonError>,
+) -> Result<BalanceClaim, SerdeJsonError> {
+    if let Some(tiers) = data["tiers"].as_array() {
+        return Ok(BalanceClaim::Tiers(
+            tiers.iter().map(parse_amount).collect::<Result<_, _>>()?,
+        ));
+    }
+    if let Some(range) = data["balance_range"].as_array() {
+        return match range.as_slice() {
+            [min, max] => Ok(BalanceClaim::Range(parse_amount(min)?, parse_amount(max)?)),
+            _ => Err(SerdeJsonError::custom(
+                "balance_range must contain a minimum and a maximum",
+            )),
+        };
+    }
+
+    let expected_balance = parse_amount(&data["expected_balance"])?;
+    match data["comparison"].as_str().unwrap_or("geq") {
+        "geq" => Ok(BalanceClaim::AtLeast(expected_balance)),
+        "leq" => Ok(BalanceClaim::AtMost(expected_balance)),
+        "eq" => Ok(BalanceClaim::Equal(expected_balance)),
+        _ => Err(SerdeJsonError::custom(
+            "Invalid comparison, expected geq, leq or eq",
+        )),
+    }
+}
+
+// Parses a predicate over account fields: {"and": [..]}, {"or": [..]}, {"not": {..}} or a
+// comparison such as {"field": "nonce", "geq": 100}. Nonce and balance are compared to amounts,
+// storage_root and code_hash to 0x-prefixed 32-byte hashes.
+pub fn parse_predicate(data: &Value) -> Result<Predicate, SerdeJsonError> {
+    let parse_all = |predicates: &Value| match predicates.as_array() {
+        Some(predicates) => predicates.iter().map(parse_predicate).collect(),
+        None => Err(SerdeJsonError::custom("Expected an array of predicates")),
+    };
+
+    if !data["and"].is_null() {
+        return Ok(Predicate::And(parse_all(&data["and"])?));
+    }
+    if !data["or"].is_null() {
+        return Ok(Predicate::Or(parse_all(&data["or"])?));
+    }
+    if !data["not"].is_null() {
+        return Ok(Predicate::Not(Box::new(parse_predicate(&data["not"])?)));
+    }
+
+    let field = match data["field"].as_str() {
+        Some("nonce") => AccountField::Nonce,
+        Some("balance") => AccountField::Balance,
+        Some("storage_root") => AccountField::StorageRoot,
+        Some("code_hash") => AccountField::CodeHash,
+        _ => return Err(SerdeJsonError::custom("Invalid predicate field")),
+    };
+    let parse_value = |value: &Value| match field {
+        AccountField::Nonce | AccountField::Balance => parse_amount(value),
+        AccountField::StorageRoot | AccountField::CodeHash => value
+            .as_str()
+            .and_then(|hash| prefix_hex::decode::<[u8; 32]>(hash).ok())
+            .ok_or(SerdeJsonError::custom("Invalid predicate hash")),
+    };
+
+    if !data["geq"].is_null() {
+        Ok(Predicate::Geq(field, parse_value(&data["geq"])?))
+    } else if !data["leq"].is_null() {
+        Ok(Predicate::Leq(field, parse_value(&data["leq"])?))
+    } else if !data["eq"].is_null() {
+        Ok(Predicate::Eq(field, parse_value(&data["eq"])?))
+    } else {
+        Err(SerdeJsonError::custom(
+            "Predicate comparison must be geq, leq or eq",
+        ))
+    }
+}
+
+// Parses an amount in base units, given either as a number or as a decimal string for amounts
+// that do not fit in a u64, into a 32-byte big-endian word
+pub fn parse_amount(value: &Value) -> Result<[u8; 32], SerdeJsonError> {
+    let amount = match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(string) => U256::from_dec_str(string).ok(),
+        _ => None,
+    }
+    .ok_or(SerdeJsonError::custom("Invalid amount"))?;
+
+    let mut amount_bytes = [0_u8; 32];
+    amount.to_big_endian(&mut amount_bytes);
+    Ok(amount_bytes)
+}
+
+// Parses an amount in the token's human units, given either as a number or as a string such as
+// "1500.25 USDC", into a 32-byte big-endian word in base units
+pub fn parse_token_amount(value: &Value, token: &TokenInfo) -> Result<[u8; 32], SerdeJsonError> {
+    let amount = match value {
+        Value::Number(number) => token.parse_units(&number.to_string()),
+        Value::String(string) => token.parse_units(string),
+        _ => None,
+    }
+    .ok_or(SerdeJsonError::custom("Invalid token amount"))?;
+
+    let mut amount_bytes = [0_u8; 32];
+    amount.to_big_endian(&mut amount_bytes);
+    Ok(amount_bytes)
+}
+
+pub fn update_json_fields(filename: &str, fields: &[(&str, Value)]) -> std::io::Result<()> {
+    let mut data = read_json_file(filename)?;
+    for (key, value) in fields {
+        data[*key] = value.clone();
+    }
+
+    let json = serde_json::to_string_pretty(&data).unwrap();
+    let mut file = File::create(filename)?;
+    file.write_all(json.as_bytes())?;
+
+    Ok(())
+}
+
 pub fn parse_json_receipt(path: &str) -> Result<Receipt, SerdeJsonError> {
     let data = read_json_file(path)?;
 
@@ -105,7 +1056,15 @@ pub fn parse_json_receipt(path: &str) -> Result<Receipt, SerdeJsonError> {
     Ok(Receipt::new(&journal?, &seal?))
 }
 
-fn read_json_file(filename: &str) -> Result<Value, SerdeJsonError> {
+pub fn read_file(filename: &str) -> std::io::Result<String> {
+    let mut file = File::open(filename)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    Ok(contents)
+}
+
+pub fn read_json_file(filename: &str) -> Result<Value, SerdeJsonError> {
     let mut file = File::open(filename).expect("Unable to open the file");
     let mut contents = String::new();
     file.read_to_string(&mut contents)
@@ -116,9 +1075,11 @@ fn read_json_file(filename: &str) -> Result<Value, SerdeJsonError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{parse_json_contract, parse_json_native, write_json};
-    use proof_core::eth_utils::format_eth_message;
+    use crate::keystore::PBKDF2_KEYSTORE;
+    use crate::{parse_json_aggregate, parse_json_contract, parse_json_native, write_json};
+    use proof_core::eth_utils::{format_eth_message, EMPTY_CODE_HASH};
     use serde_json::json;
+    use sha3::{Digest, Keccak256};
     use std::fs::File;
     use std::io::Write;
     use std::path::Path;
@@ -155,6 +1116,112 @@ mod tests {
         assert_eq!(contents["key"], "value");
     }
 
+    #[test]
+    fn test_parse_amount() {
+        let mut expected = [0_u8; 32];
+        expected[30..].copy_from_slice(&[3, 232]);
+
+        assert_eq!(parse_amount(&json!(1000)).unwrap(), expected);
+        assert_eq!(parse_amount(&json!("1000")).unwrap(), expected);
+        assert_eq!(
+            parse_amount(&json!("100000000000000000000"))
+                .unwrap()
+                .to_vec(),
+            prefix_hex::decode::<Vec<u8>>(
+                "0x0000000000000000000000000000000000000000000000056bc75e2d63100000"
+            )
+            .unwrap()
+        );
+        assert!(parse_amount(&json!("-1")).is_err());
+        assert!(parse_amount(&json!(null)).is_err());
+    }
+
+    #[test]
+    fn test_parse_balance_claim_tiers() {
+        let data = json!({
+            "expected_balance": 1000,
+            "tiers": [1, "10", "100"]
+        });
+
+        assert_eq!(
+            parse_balance_claim(&data).unwrap(),
+            BalanceClaim::Tiers(vec![
+                parse_amount(&json!(1)).unwrap(),
+                parse_amount(&json!(10)).unwrap(),
+                parse_amount(&json!(100)).unwrap(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_balance_claim_comparison() {
+        let data = json!({ "expected_balance": 0, "comparison": "leq" });
+        assert_eq!(
+            parse_balance_claim(&data).unwrap(),
+            BalanceClaim::AtMost([0_u8; 32])
+        );
+
+        let data = json!({ "expected_balance": 1000, "balance_range": [10, "100"] });
+        assert_eq!(
+            parse_balance_claim(&data).unwrap(),
+            BalanceClaim::Range(
+                parse_amount(&json!(10)).unwrap(),
+                parse_amount(&json!(100)).unwrap()
+            )
+        );
+
+        assert!(
+            parse_balance_claim(&json!({ "expected_balance": 1, "comparison": "lt" })).is_err()
+        );
+        assert!(parse_balance_claim(&json!({ "balance_range": [1] })).is_err());
+    }
+
+    #[test]
+    fn test_parse_predicate() {
+        let data = json!({
+            "and": [
+                { "field": "nonce", "geq": 100 },
+                { "field": "balance", "geq": "1000000000000000000" },
+                { "not": { "field": "code_hash", "leq": "0x0000000000000000000000000000000000000000000000000000000000000001" } },
+                { "or": [{ "field": "code_hash", "eq": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470" }] }
+            ]
+        });
+        let mut one = [0_u8; 32];
+        one[31] = 1;
+
+        assert_eq!(
+            parse_predicate(&data).unwrap(),
+            Predicate::And(vec![
+                Predicate::Geq(AccountField::Nonce, parse_amount(&json!(100)).unwrap()),
+                Predicate::Geq(
+                    AccountField::Balance,
+                    parse_amount(&json!("1000000000000000000")).unwrap()
+                ),
+                Predicate::Not(Box::new(Predicate::Leq(AccountField::CodeHash, one))),
+                Predicate::Or(vec![Predicate::Eq(AccountField::CodeHash, EMPTY_CODE_HASH)]),
+            ])
+        );
+        assert!(parse_predicate(&json!({ "field": "code", "eq": 1 })).is_err());
+        assert!(parse_predicate(&json!({ "field": "nonce", "gt": 1 })).is_err());
+        assert!(parse_predicate(&json!({ "field": "code_hash", "eq": "0x01" })).is_err());
+    }
+
+    #[test]
+    fn test_update_json_fields() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("test_update.json");
+        let mut file = File::create(&file_path).unwrap();
+        file.write_all(br#"{ "message": "hello world", "signature": "" }"#)
+            .unwrap();
+
+        let file_path = file_path.to_str().unwrap();
+        update_json_fields(file_path, &[("signature", json!("0x01a3"))]).unwrap();
+        let contents = read_json_file(file_path).unwrap();
+
+        assert_eq!(contents["message"], "hello world");
+        assert_eq!(contents["signature"], "0x01a3");
+    }
+
     #[test]
     fn test_parse_json_native() {
         let temp_dir = tempdir().unwrap();
@@ -166,7 +1233,8 @@ mod tests {
             "block_number": "12345",
             "signature": "test_signature",
             "message": "test_message",
-            "expected_balance": 1000
+            "expected_balance": 1000,
+            "address_salt": "test_address_salt"
         }
         "#;
 
@@ -182,7 +1250,405 @@ mod tests {
             native_request.message,
             format_eth_message("test_message".to_string())
         );
-        assert_eq!(native_request.expected_balance, 1000);
+        assert_eq!(
+            native_request.balance_claim,
+            BalanceClaim::AtLeast(parse_amount(&json!(1000)).unwrap())
+        );
+        assert_eq!(
+            native_request.address_salt.as_deref(),
+            Some("test_address_salt")
+        );
+    }
+
+    #[test]
+    fn test_parse_json_aggregate() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("test_parse_aggregate.json");
+        let test_data = r#"
+        {
+            "provider": "test_provider",
+            "block_number": "12345",
+            "message": "test_message",
+            "expected_balance": 1000,
+            "derivation_path": "m/44'/60'/0'/0/0..2",
+            "accounts": [
+                { "user_address": "test_user_address_0", "signature": "test_signature_0" },
+                { "user_address": "test_user_address_1", "signature": "test_signature_1" }
+            ]
+        }
+        "#;
+
+        let mut file = File::create(&file_path).unwrap();
+        file.write_all(test_data.as_bytes()).unwrap();
+        let aggregate_request = parse_json_aggregate(file_path.to_str().unwrap()).unwrap();
+
+        assert_eq!(aggregate_request.provider, "test_provider");
+        assert_eq!(aggregate_request.block_number, "12345");
+        assert_eq!(
+            aggregate_request.message,
+            format_eth_message("test_message".to_string())
+        );
+        assert_eq!(
+            aggregate_request.balance_claim,
+            BalanceClaim::AtLeast(parse_amount(&json!(1000)).unwrap())
+        );
+        assert_eq!(aggregate_request.accounts.len(), 2);
+        assert_eq!(
+            aggregate_request.accounts[1].user_address,
+            "test_user_address_1"
+        );
+        assert_eq!(aggregate_request.accounts[1].signature, "test_signature_1");
+    }
+
+    #[test]
+    fn test_parse_json_inactivity() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("test_parse_inactivity.json");
+        let test_data = r#"
+        {
+            "provider": "test_provider",
+            "user_address": "test_user_address",
+            "start_block": "0x1000",
+            "end_block": "latest",
+            "signature": "test_signature",
+            "message": "test_message",
+            "expected_balance": "1000"
+        }
+        "#;
+
+        let mut file = File::create(&file_path).unwrap();
+        file.write_all(test_data.as_bytes()).unwrap();
+        let inactivity_request = parse_json_inactivity(file_path.to_str().unwrap()).unwrap();
+
+        assert_eq!(inactivity_request.start_block, "0x1000");
+        assert_eq!(inactivity_request.end_block, "latest");
+        assert_eq!(
+            inactivity_request.expected_balance,
+            parse_amount(&json!(1000)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_json_owner() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("test_parse_owner.json");
+        let test_data = r#"
+        {
+            "provider": "test_provider",
+            "user_address": "test_user_address",
+            "block_number": "12345",
+            "signature": "test_signature",
+            "message": "test_message",
+            "contract_address": "test_contract_address",
+            "owner_slot": "eip1967_admin"
+        }
+        "#;
+
+        let mut file = File::create(&file_path).unwrap();
+        file.write_all(test_data.as_bytes()).unwrap();
+        let owner_request = parse_json_owner(file_path.to_str().unwrap()).unwrap();
+
+        assert_eq!(owner_request.contract_address, "test_contract_address");
+        assert_eq!(owner_request.owner_slot, EIP1967_ADMIN_SLOT);
+    }
+
+    #[test]
+    fn test_eip1967_admin_slot() {
+        let hash = U256::from_big_endian(&Keccak256::digest(b"eip1967.proxy.admin"));
+        let slot: [u8; 32] = prefix_hex::decode(EIP1967_ADMIN_SLOT).unwrap();
+        assert_eq!(U256::from_big_endian(&slot), hash - 1);
+    }
+
+    #[test]
+    fn test_parse_json_usd() {
+        let temp_file = create_request_file(json!({
+            "aggregator_address": "test_aggregator_address",
+            "hot_vars_slot": 43,
+            "round_id_offset": 22,
+            "transmissions_slot": "0x000000000000000000000000000000000000000000000000000000000000002c",
+            "feed_decimals": 8,
+            "usd_threshold": 1000000
+        }));
+        let usd_request = parse_json_usd(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(usd_request.token, None);
+        assert_eq!(usd_request.asset_decimals, 18);
+        assert_eq!(usd_request.aggregator_layout.hot_vars_slot[31], 43);
+        assert_eq!(usd_request.aggregator_layout.round_id_offset, 22);
+        assert_eq!(usd_request.aggregator_layout.transmissions_slot[31], 44);
+        assert_eq!(usd_request.feed_decimals, 8);
+        assert_eq!(usd_request.max_age, 3600);
+        assert_eq!(usd_request.usd_threshold, 1_000_000);
+        assert!(parse_slot(&json!("0x2c")).is_err());
+    }
+
+    #[test]
+    fn test_parse_json_supply_share() {
+        let temp_file = create_request_file(json!({
+            "token": "DAI",
+            "bps": 500
+        }));
+        let request = parse_json_supply_share(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            request.contract_address,
+            "0x6b175474e89094c44da98b954eedeac495271d0f"
+        );
+        assert_eq!(
+            request.total_supply_slot,
+            "0x0000000000000000000000000000000000000000000000000000000000000001"
+        );
+        assert_eq!(request.bps, 500);
+
+        let temp_file = create_request_file(json!({
+            "token": "WETH",
+            "bps": 500
+        }));
+        assert!(parse_json_supply_share(temp_file.path().to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_parse_json_vault() {
+        let temp_file = create_request_file(json!({
+            "vault_address": "test_vault_address",
+            "balance_slot": "test_balance_slot",
+            "total_supply_slot": "test_total_supply_slot",
+            "underlying_token": "DAI",
+            "threshold": "1500.5"
+        }));
+        let request = parse_json_vault(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            request.assets,
+            VaultAssetsSource::Held(TokenAsset {
+                contract_address: String::from("0x6b175474e89094c44da98b954eedeac495271d0f"),
+                balance_slot: String::from(
+                    "0x0000000000000000000000000000000000000000000000000000000000000002"
+                ),
+                layout: SlotLayout::Solidity,
+            })
+        );
+        assert_eq!(
+            U256::from_big_endian(&request.threshold),
+            U256::from_dec_str("1500500000000000000000").unwrap()
+        );
+
+        let temp_file = create_request_file(json!({
+            "vault_address": "test_vault_address",
+            "balance_slot": "test_balance_slot",
+            "layout": "vyper",
+            "total_supply_slot": "test_total_supply_slot",
+            "total_assets_slots": [5, 6],
+            "decimals_offset": 3,
+            "threshold": 1000
+        }));
+        let request = parse_json_vault(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(request.layout, SlotLayout::Vyper);
+        assert_eq!(request.decimals_offset, 3);
+        assert_eq!(
+            request.assets,
+            VaultAssetsSource::Stored(vec![
+                String::from("0x0000000000000000000000000000000000000000000000000000000000000005"),
+                String::from("0x0000000000000000000000000000000000000000000000000000000000000006"),
+            ])
+        );
+        assert_eq!(U256::from_big_endian(&request.threshold), U256::from(1000));
+    }
+
+    #[test]
+    fn test_parse_json_steth() {
+        let temp_file = create_request_file(json!({
+            "wrapped": true,
+            "cl_balance_position": 7,
+            "threshold": "32000000000000000000"
+        }));
+        let request = parse_json_steth(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(request.steth_address, STETH_ADDRESS);
+        assert_eq!(request.lido_layout.shares_slot, [0_u8; 32]);
+        assert_eq!(
+            request.lido_layout.total_shares_position,
+            <[u8; 32]>::from(Keccak256::digest(b"lido.StETH.totalShares"))
+        );
+        assert_eq!(request.lido_layout.cl_balance_position[31], 7);
+        assert_eq!(request.wrapped.unwrap().contract_address, WSTETH_ADDRESS);
+        assert_eq!(
+            U256::from_big_endian(&request.threshold),
+            U256::exp10(18) * 32
+        );
+    }
+
+    #[test]
+    fn test_parse_json_aave() {
+        let temp_file = create_request_file(json!({
+            "asset": "USDC",
+            "threshold": "2500.5"
+        }));
+        let request = parse_json_aave(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(request.pool_address, AAVE_V3_POOL_ADDRESS);
+        assert_eq!(
+            request.asset_address,
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        );
+        assert_eq!(
+            request.reserves_slot,
+            "0x0000000000000000000000000000000000000000000000000000000000000034"
+        );
+        assert_eq!(request.user_state_slot, request.reserves_slot);
+        assert_eq!(request.a_token_address, None);
+        assert_eq!(
+            U256::from_big_endian(&request.threshold),
+            U256::from(2_500_500_000_u64)
+        );
+    }
+
+    #[test]
+    fn test_parse_json_lp() {
+        let temp_file = create_request_file(json!({
+            "pair_address": "test_pair_address",
+            "reserves_slot": 9,
+            "threshold0": 1000,
+            "threshold1": "1000000000000000000000"
+        }));
+        let request = parse_json_lp(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(request.pair_layout.total_supply_slot, [0_u8; 32]);
+        assert_eq!(request.pair_layout.balance_slot[31], 1);
+        assert_eq!(request.pair_layout.token0_slot[31], 6);
+        assert_eq!(request.pair_layout.token1_slot[31], 7);
+        assert_eq!(request.pair_layout.reserves_slot[31], 9);
+        assert_eq!(U256::from_big_endian(&request.threshold0), U256::from(1000));
+        assert_eq!(U256::from_big_endian(&request.threshold1), U256::exp10(21));
+    }
+
+    #[test]
+    fn test_parse_json_ctoken() {
+        let temp_file = create_request_file(json!({
+            "ctoken_address": "test_ctoken_address",
+            "underlying_token": "USDC",
+            "threshold": "100"
+        }));
+        let request = parse_json_ctoken(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(request.ctoken_layout.total_supply_slot[31], 13);
+        assert_eq!(request.ctoken_layout.balance_slot[31], 14);
+        assert_eq!(request.ctoken_layout.initial_exchange_rate_slot[31], 7);
+        assert_eq!(
+            request.underlying.unwrap().contract_address,
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        );
+        assert_eq!(
+            U256::from_big_endian(&request.threshold),
+            U256::from(100_000_000)
+        );
+
+        let temp_file = create_request_file(json!({
+            "ctoken_address": "test_ctoken_address",
+            "balance_slot": 15,
+            "threshold": "100"
+        }));
+        let request = parse_json_ctoken(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(request.ctoken_layout.balance_slot[31], 15);
+        assert_eq!(request.underlying, None);
+        assert_eq!(U256::from_big_endian(&request.threshold), U256::from(100));
+    }
+
+    #[test]
+    fn test_parse_json_votes() {
+        let temp_file = create_request_file(json!({
+            "token_address": "test_token_address",
+            "checkpoints_slot": 8,
+            "threshold": 1000
+        }));
+        let request = parse_json_votes(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            request.checkpoints_slot,
+            "0x0000000000000000000000000000000000000000000000000000000000000008"
+        );
+        assert_eq!(request.key_bytes, 4);
+        assert_eq!(U256::from_big_endian(&request.threshold), U256::from(1000));
+
+        let temp_file = create_request_file(json!({
+            "token": "DAI",
+            "checkpoints_slot": 8,
+            "checkpoint_key_bytes": 6,
+            "threshold": "1.5"
+        }));
+        let request = parse_json_votes(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(request.key_bytes, 6);
+        assert_eq!(
+            U256::from_big_endian(&request.threshold),
+            U256::exp10(17) * U256::from(15)
+        );
+    }
+
+    #[test]
+    fn test_parse_json_vote() {
+        let temp_dir = tempdir().unwrap();
+        let keystore_path = temp_dir.path().join("keystore.json");
+        let mut keystore_file = File::create(&keystore_path).unwrap();
+        keystore_file.write_all(PBKDF2_KEYSTORE.as_bytes()).unwrap();
+        let keystore = keystore_path.to_str().unwrap();
+
+        let temp_file = create_temp_json_file(&json!({
+            "provider": "test_provider",
+            "block_number": "12345",
+            "keystore": keystore,
+            "poll": "treasury-2024",
+            "choice": 2,
+            "token": "USDC",
+            "tiers": ["0", "1000", "100000"]
+        }));
+        let passphrase = || Ok(String::from("testpassword"));
+        let request = parse_json_vote(temp_file.path().to_str().unwrap(), passphrase).unwrap();
+
+        assert_eq!(request.poll_id, poll_id("treasury-2024"));
+        assert_eq!(request.choice, 2);
+        match request.ownership {
+            VoteOwnership::PrivateKey(private_key) => assert_eq!(
+                request.user_address,
+                prefix_hex::encode(private_key_to_address(&private_key).unwrap().to_vec())
+            ),
+            VoteOwnership::Signature { .. } => panic!("Keystore votes should hold the key"),
+        }
+        assert_eq!(
+            request.token.unwrap().contract_address,
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        );
+        let tiers = request.tiers.unwrap();
+        assert_eq!(tiers[0], [0_u8; 32]);
+        assert_eq!(U256::from_big_endian(&tiers[1]), U256::from(1_000_000_000));
+
+        let temp_file = create_temp_json_file(&json!({
+            "provider": "test_provider",
+            "block_number": "12345",
+            "keystore": keystore,
+            "poll": "treasury-2024",
+            "choice": -1
+        }));
+        assert!(parse_json_vote(temp_file.path().to_str().unwrap(), passphrase).is_err());
+
+        // signed votes never ask for a passphrase
+        let temp_file = create_request_file(json!({
+            "poll": "treasury-2024",
+            "choice": 1
+        }));
+        let request = parse_json_vote(temp_file.path().to_str().unwrap(), || {
+            Err("No passphrase".into())
+        })
+        .unwrap();
+        match request.ownership {
+            VoteOwnership::Signature { message, .. } => assert_eq!(
+                message,
+                format_eth_message(vote_message(&poll_id("treasury-2024"), 1))
+            ),
+            VoteOwnership::PrivateKey(_) => panic!("Signed votes should hold the signature"),
+        }
     }
 
     #[test]
@@ -214,9 +1680,73 @@ mod tests {
             contract_request.message,
             format_eth_message("test_message".to_string())
         );
-        assert_eq!(contract_request.expected_balance, 1000);
+        assert_eq!(
+            contract_request.balance_claim,
+            BalanceClaim::AtLeast(parse_amount(&json!(1000)).unwrap())
+        );
         assert_eq!(contract_request.contract_address, "test_contract_address");
         assert_eq!(contract_request.balance_slot, "test_balance_slot");
+        assert_eq!(contract_request.layout, SlotLayout::Solidity);
+        assert_eq!(contract_request.address_salt, None);
+        assert_eq!(contract_request.balance_blinding, None);
+        assert_eq!(contract_request.blacklist, None);
+    }
+
+    #[test]
+    fn test_parse_json_contract_token() {
+        let temp_file = create_request_file(json!({
+            "token": "USDC",
+            "expected_balance": "1500.25 USDC"
+        }));
+        let contract_request = parse_json_contract(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            contract_request.contract_address,
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        );
+        assert_eq!(
+            contract_request.balance_slot,
+            "0x0000000000000000000000000000000000000000000000000000000000000009"
+        );
+        assert_eq!(
+            contract_request.balance_claim,
+            BalanceClaim::AtLeast(parse_amount(&json!(1_500_250_000_u64)).unwrap())
+        );
+
+        let temp_file = create_request_file(json!({
+            "chain_id": 5,
+            "token": "USDC",
+            "expected_balance": 1
+        }));
+        assert!(parse_json_contract(temp_file.path().to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_parse_json_contract_blacklist() {
+        let request = |mut fields: Value| {
+            fields["expected_balance"] = json!(1);
+            let temp_file = create_request_file(fields);
+            parse_json_contract(temp_file.path().to_str().unwrap())
+        };
+
+        // registry tokens always come with their blacklist, so that USDC balances are masked
+        let usdc = request(json!({ "token": "USDC" })).unwrap();
+        assert_eq!(usdc.blacklist, Some(BlacklistLayout::BalanceHighBit));
+
+        let mut slot = [0_u8; 32];
+        slot[31] = 6;
+        let usdt = request(json!({ "token": "USDT" })).unwrap();
+        assert_eq!(usdt.blacklist, Some(BlacklistLayout::Mapping(slot)));
+        let custom = request(json!({
+            "contract_address": "test_contract_address",
+            "balance_slot": "test_balance_slot",
+            "blacklist_slot": 6
+        }))
+        .unwrap();
+        assert_eq!(custom.blacklist, Some(BlacklistLayout::Mapping(slot)));
+
+        // DAI cannot freeze balances
+        assert_eq!(request(json!({ "token": "DAI" })).unwrap().blacklist, None);
     }
 
     fn create_temp_json_file(json_content: &serde_json::Value) -> NamedTempFile {
@@ -228,6 +1758,21 @@ mod tests {
         temp_file
     }
 
+    // Request file of a signed mode, the common fields followed by the mode's own
+    fn create_request_file(fields: Value) -> NamedTempFile {
+        let mut data = json!({
+            "provider": "test_provider",
+            "user_address": "test_user_address",
+            "block_number": "12345",
+            "signature": "test_signature",
+            "message": "test_message"
+        });
+        data.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        create_temp_json_file(&data)
+    }
+
     #[test]
     fn test_parse_json_receipt_valid() {
         let valid_json = json!({
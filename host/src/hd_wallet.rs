@@ -0,0 +1,87 @@
+use bip32::{ChildNumber, DerivationPath, XPrv};
+use bip39::Mnemonic;
+use std::ops::Range;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Environment variable holding the BIP-39 mnemonic (can be set in a .env file)
+pub const MNEMONIC_VAR: &str = "MNEMONIC";
+
+pub fn read_mnemonic() -> Result<String> {
+    dotenv::dotenv().ok();
+    match std::env::var(MNEMONIC_VAR) {
+        Ok(mnemonic) => Ok(mnemonic),
+        Err(_) => Ok(rpassword::prompt_password("Mnemonic: ")?),
+    }
+}
+
+// Parses a derivation path whose last component is a range of non-hardened indices,
+// e.g. "m/44'/60'/0'/0/0..100" (end excluded)
+pub fn parse_path_range(path: &str) -> Result<(DerivationPath, Range<u32>)> {
+    let (parent, range) = path
+        .rsplit_once('/')
+        .ok_or("Derivation path must end with an index range")?;
+    let (start, end) = range
+        .split_once("..")
+        .ok_or("Derivation path must end with an index range, e.g. 0..100")?;
+    let range = start.parse::<u32>()?..end.parse::<u32>()?;
+    if range.is_empty() {
+        return Err("Derivation index range is empty".into());
+    }
+
+    Ok((parent.parse()?, range))
+}
+
+// Derives the private keys of all indices in the path range from a BIP-39 mnemonic
+pub fn derive_private_keys(mnemonic: &str, path: &str) -> Result<Vec<[u8; 32]>> {
+    let (parent_path, range) = parse_path_range(path)?;
+    let mnemonic = Mnemonic::parse(mnemonic.trim())?;
+    let parent = XPrv::derive_from_path(mnemonic.to_seed(""), &parent_path)?;
+
+    range
+        .map(|index| {
+            Ok(parent
+                .derive_child(ChildNumber::new(index, false)?)?
+                .to_bytes())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proof_core::eth_utils::private_key_to_address;
+
+    const TEST_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_parse_path_range() {
+        let (parent, range) = parse_path_range("m/44'/60'/0'/0/5..10").unwrap();
+
+        assert_eq!(parent.to_string(), "m/44'/60'/0'/0");
+        assert_eq!(range, 5..10);
+    }
+
+    #[test]
+    fn test_parse_path_range_invalid() {
+        assert!(parse_path_range("m/44'/60'/0'/0/0").is_err());
+        assert!(parse_path_range("m/44'/60'/0'/0/10..5").is_err());
+    }
+
+    #[test]
+    fn test_derive_private_keys() {
+        let keys = derive_private_keys(TEST_MNEMONIC, "m/44'/60'/0'/0/0..2").unwrap();
+        let addresses: Vec<String> = keys
+            .iter()
+            .map(|key| prefix_hex::encode(private_key_to_address(key).unwrap().to_vec()))
+            .collect();
+
+        assert_eq!(
+            addresses,
+            vec![
+                "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+            ]
+        );
+    }
+}
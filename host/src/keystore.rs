@@ -0,0 +1,186 @@
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use hmac::Hmac;
+use proof_core::eth_utils::private_key_to_address;
+use serde::Deserialize;
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+
+use std::fs::File;
+use std::io::Read;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+// Environment variable holding the keystore passphrase (can be set in a .env file)
+pub const KEYSTORE_PASSPHRASE_VAR: &str = "KEYSTORE_PASSPHRASE";
+
+// Ethereum V3 keystore (Web3 Secret Storage) file
+#[derive(Debug, Deserialize)]
+struct KeystoreFile {
+    // some clients capitalize this field ("Crypto")
+    #[serde(alias = "Crypto")]
+    crypto: KeystoreCrypto,
+    version: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    #[serde(flatten)]
+    kdf: KdfParams,
+    mac: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+enum KdfParams {
+    Scrypt {
+        dklen: usize,
+        n: u32,
+        r: u32,
+        p: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        dklen: usize,
+        c: u32,
+        prf: String,
+        salt: String,
+    },
+}
+
+pub fn read_passphrase() -> Result<String> {
+    dotenv::dotenv().ok();
+    // Automated environments provide the passphrase through the environment, otherwise prompt for it
+    match std::env::var(KEYSTORE_PASSPHRASE_VAR) {
+        Ok(passphrase) => Ok(passphrase),
+        Err(_) => Ok(rpassword::prompt_password("Keystore passphrase: ")?),
+    }
+}
+
+pub fn load_keystore(filename: &str, passphrase: &str) -> Result<[u8; 32]> {
+    let mut file = File::open(filename)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    decrypt_keystore(&contents, passphrase)
+}
+
+pub fn decrypt_keystore(keystore: &str, passphrase: &str) -> Result<[u8; 32]> {
+    let keystore: KeystoreFile = serde_json::from_str(keystore)?;
+    if keystore.version != 3 {
+        return Err(format!("Unsupported keystore version {}", keystore.version).into());
+    }
+    let crypto = keystore.crypto;
+    if crypto.cipher != "aes-128-ctr" {
+        return Err(format!("Unsupported keystore cipher {}", crypto.cipher).into());
+    }
+
+    let derived_key = derive_key(&crypto.kdf, passphrase.as_bytes())?;
+    let mut ciphertext = hex::decode(&crypto.ciphertext)?;
+
+    // The MAC is computed over the second half of the derived key and the ciphertext
+    let mac = Keccak256::digest([&derived_key[16..32], ciphertext.as_slice()].concat());
+    if mac.as_slice() != hex::decode(&crypto.mac)?.as_slice() {
+        return Err("Keystore MAC mismatch: wrong passphrase or corrupted file".into());
+    }
+
+    // Decrypt in place with the first half of the derived key
+    let iv = hex::decode(&crypto.cipherparams.iv)?;
+    let mut cipher = Aes128Ctr::new_from_slices(&derived_key[..16], &iv)
+        .map_err(|_| "Invalid keystore cipher parameters")?;
+    cipher.apply_keystream(&mut ciphertext);
+
+    let private_key: [u8; 32] = ciphertext
+        .try_into()
+        .map_err(|_| "Keystore does not contain a 32-byte private key")?;
+    // Make sure the decrypted key is a valid secp256k1 scalar
+    private_key_to_address(&private_key)?;
+
+    Ok(private_key)
+}
+
+fn derive_key(kdf: &KdfParams, passphrase: &[u8]) -> Result<Vec<u8>> {
+    match kdf {
+        KdfParams::Scrypt {
+            dklen,
+            n,
+            r,
+            p,
+            salt,
+        } => {
+            if !n.is_power_of_two() || *dklen < 32 {
+                return Err("Invalid scrypt parameters".into());
+            }
+            let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p)
+                .map_err(|_| "Invalid scrypt parameters")?;
+            let mut derived_key = vec![0_u8; *dklen];
+            scrypt::scrypt(passphrase, &hex::decode(salt)?, &params, &mut derived_key)
+                .map_err(|_| "Invalid scrypt output length")?;
+            Ok(derived_key)
+        }
+        KdfParams::Pbkdf2 {
+            dklen,
+            c,
+            prf,
+            salt,
+        } => {
+            if prf != "hmac-sha256" || *dklen < 32 {
+                return Err("Invalid pbkdf2 parameters".into());
+            }
+            let mut derived_key = vec![0_u8; *dklen];
+            pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase, &hex::decode(salt)?, *c, &mut derived_key);
+            Ok(derived_key)
+        }
+    }
+}
+
+// Test vector from the Web3 Secret Storage definition, encrypted with "testpassword"
+#[cfg(test)]
+pub const PBKDF2_KEYSTORE: &str = r#"
+{
+    "crypto": {
+        "cipher": "aes-128-ctr",
+        "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+        "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+        "kdf": "pbkdf2",
+        "kdfparams": {
+            "c": 262144,
+            "dklen": 32,
+            "prf": "hmac-sha256",
+            "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+        },
+        "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+    },
+    "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+    "version": 3
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decrypt_keystore_pbkdf2() {
+        let private_key = decrypt_keystore(PBKDF2_KEYSTORE, "testpassword").unwrap();
+
+        assert_eq!(
+            hex::encode(private_key),
+            "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+        );
+    }
+
+    #[test]
+    fn test_decrypt_keystore_wrong_passphrase() {
+        assert!(decrypt_keystore(PBKDF2_KEYSTORE, "wrongpassword").is_err());
+    }
+}
@@ -1,73 +1,515 @@
+mod address_tree;
+mod challenge;
 mod ethereum;
 mod file_utils;
+mod hd_wallet;
+mod keystore;
 mod prover;
+mod signer;
+mod tally;
+mod token_registry;
 mod verifier;
 
-use clap::{Arg, Command};
-use file_utils::{parse_json_contract, parse_json_native, parse_json_receipt, write_json};
-use methods::{CONTRACT_PROOF_ID, NATIVE_PROOF_ID};
-use proof_core::proof_io::{ContractProofOutput, NativeProofOutput};
+use address_tree::{
+    build_address_tree, build_sorted_address_tree, read_address_csv, read_address_list,
+    write_address_tree,
+};
+use challenge::{read_challenge, write_challenge, Challenge};
+use clap::{value_parser, Arg, Command};
+use ethereum::requests::Request;
+use file_utils::{
+    parse_json_aave, parse_json_aggregate, parse_json_contract, parse_json_ctoken,
+    parse_json_inactivity, parse_json_lp, parse_json_native, parse_json_native_key,
+    parse_json_owner, parse_json_receipt, parse_json_steth, parse_json_supply_share,
+    parse_json_usd, parse_json_vault, parse_json_vote, parse_json_votes, update_json_fields,
+    write_json,
+};
+use hd_wallet::read_mnemonic;
+use keystore::{load_keystore, read_passphrase};
+use methods::{
+    AAVE_PROOF_ID, AGGREGATE_PROOF_ID, CONTRACT_PROOF_ID, CTOKEN_PROOF_ID, INACTIVITY_PROOF_ID,
+    LP_PROOF_ID, NATIVE_KEY_PROOF_ID, NATIVE_PROOF_ID, OWNER_PROOF_ID, STETH_PROOF_ID,
+    SUPPLY_SHARE_PROOF_ID, USD_PROOF_ID, VAULT_PROOF_ID, VOTES_PROOF_ID, VOTE_PROOF_ID,
+};
+use primitive_types::U256;
+use proof_core::proof_io::{
+    AaveProofOutput, AggregateProofOutput, CTokenProofOutput, ContractProofOutput,
+    InactivityProofOutput, LpProofOutput, NativeKeyProofOutput, NativeProofOutput,
+    OwnerProofOutput, ProofOutput, StethProofOutput, SupplyShareProofOutput, UsdProofOutput,
+    VaultAssets, VaultProofOutput, VoteProofOutput, VotesProofOutput,
+};
 use prover::prove_assets;
-use verifier::verify_receipt;
+use signer::{sign_aggregate_request_file, sign_request_file};
+use tally::tally_directory;
+use token_registry::TokenRegistry;
+use verifier::{check_code_hash, open_balance, reveal_address, verify_receipt};
+
+use std::fmt::Debug;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = Command::new("prove-assets")
         .version("1.0")
         .author("Nicolas Le Bel")
-        .about("CLI tool to process JSON input and execute prove_native or prove_contract commands")
+        .about("CLI tool to process JSON input and execute prove_native, prove_native_key or prove_contract commands")
         .arg(
             Arg::new("input")
                 .short('i')
                 .long("input")
                 .value_name("FILE")
-                .help("Sets the input JSON file")
-                .required(true),
+                .help("Sets the input JSON file"),
         )
         .arg(
             Arg::new("command")
                 .short('c')
                 .long("command")
                 .value_name("COMMAND")
-                .help("Sets the command to execute: prove_native/prove_contract, or verify_native/verify_contract")
+                .help("Sets the command to execute: prove_native/prove_native_key/prove_aggregate/prove_contract/prove_inactivity/prove_owner/prove_usd/prove_supply_share/prove_vault/prove_steth/prove_aave/prove_lp/prove_ctoken/prove_votes/prove_vote, verify_native/verify_native_key/verify_aggregate/verify_contract/verify_inactivity/verify_owner/verify_usd/verify_supply_share/verify_vault/verify_steth/verify_aave/verify_lp/verify_ctoken/verify_votes/verify_vote, tally, build_address_allowlist/build_sanctions_tree, sign/sign_mnemonic, challenge, or reveal/open_balance")
                 .required(true),
         )
+        .arg(
+            Arg::new("keystore")
+                .short('k')
+                .long("keystore")
+                .value_name("FILE")
+                .help("Sets the Ethereum V3 keystore file used by the sign command"),
+        )
+        .arg(
+            Arg::new("challenge")
+                .long("challenge")
+                .value_name("FILE")
+                .help("Sets the challenge JSON file: written by challenge, answered by sign/sign_mnemonic, checked by verify commands"),
+        )
+        .arg(
+            Arg::new("verifier")
+                .long("verifier")
+                .value_name("NAME")
+                .help("Sets the verifier identity included in a new challenge"),
+        )
+        .arg(
+            Arg::new("validity")
+                .long("validity")
+                .value_name("SECONDS")
+                .value_parser(value_parser!(u64))
+                .default_value("3600")
+                .help("Sets how long a new challenge remains valid"),
+        )
+        .arg(
+            Arg::new("address")
+                .long("address")
+                .value_name("ADDRESS")
+                .help("Sets the address revealed by the reveal command"),
+        )
+        .arg(
+            Arg::new("salt")
+                .long("salt")
+                .value_name("HEX")
+                .help("Sets the address salt used by the reveal command"),
+        )
+        .arg(
+            Arg::new("balance")
+                .long("balance")
+                .value_name("WEI")
+                .help("Sets the exact balance (decimal, in base units) opened by the open_balance command"),
+        )
+        .arg(
+            Arg::new("blinding")
+                .long("blinding")
+                .value_name("HEX")
+                .help("Sets the balance blinding factor used by the open_balance command"),
+        )
+        .arg(
+            Arg::new("code_hash_allowlist")
+                .long("code-hash-allowlist")
+                .value_name("FILE")
+                .help("Sets the JSON file mapping token names to code hashes checked by verify_contract, verify_lp and verify_votes"),
+        )
+        .arg(
+            Arg::new("registry")
+                .long("registry")
+                .value_name("FILE")
+                .help("Sets the token registry used to print verify_contract amounts (defaults to the bundled registry)"),
+        )
+        .arg(
+            Arg::new("token")
+                .long("token")
+                .value_name("NAME")
+                .help("Sets the token of the code hash allowlist the verified contract must match"),
+        )
         .get_matches();
 
-    let input_file = matches.get_one::<String>("input").unwrap().as_str();
+    let input_file = || {
+        matches
+            .get_one::<String>("input")
+            .map(String::as_str)
+            .ok_or("This command requires an --input file")
+    };
+    let challenge_file = matches.get_one::<String>("challenge");
+    let load_registry =
+        || TokenRegistry::load(matches.get_one::<String>("registry").map(String::as_str));
+    // with --code-hash-allowlist, rejects a contract whose committed code hash is not listed
+    let check_allowlist = |code_hash: &[u8; 32]| -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(code_hash_allowlist) = matches.get_one::<String>("code_hash_allowlist") {
+            let token = matches.get_one::<String>("token").map(String::as_str);
+            let token = check_code_hash(code_hash_allowlist, code_hash, token)?;
+            println!(
+                "Contract code hash matches token {} of the code hash allowlist",
+                token
+            );
+        }
+        Ok(())
+    };
     let command = matches.get_one::<String>("command").unwrap().as_str();
 
     match command {
-        "prove_native" => {
-            let request = parse_json_native(input_file)?;
-            let receipt = prove_assets(&request)?;
+        "prove_native" => prove_and_write(&parse_json_native(input_file()?)?)?,
+        "prove_native_key" => {
+            let mut request = parse_json_native_key(input_file()?, &read_passphrase()?)?;
+            // nothing is signed with the key, so the challenge message is committed as the context
+            if let Some(challenge_file) = challenge_file {
+                request.context = read_challenge(challenge_file)?.to_message();
+            }
+            prove_and_write(&request)?
+        }
+        "prove_aggregate" => prove_and_write(&parse_json_aggregate(input_file()?)?)?,
+        "prove_contract" => prove_and_write(&parse_json_contract(input_file()?)?)?,
+        "prove_inactivity" => prove_and_write(&parse_json_inactivity(input_file()?)?)?,
+        "prove_owner" => prove_and_write(&parse_json_owner(input_file()?)?)?,
+        "prove_usd" => prove_and_write(&parse_json_usd(input_file()?)?)?,
+        "prove_supply_share" => prove_and_write(&parse_json_supply_share(input_file()?)?)?,
+        "prove_vault" => prove_and_write(&parse_json_vault(input_file()?)?)?,
+        "prove_steth" => prove_and_write(&parse_json_steth(input_file()?)?)?,
+        "prove_aave" => prove_and_write(&parse_json_aave(input_file()?)?)?,
+        "prove_lp" => prove_and_write(&parse_json_lp(input_file()?)?)?,
+        "prove_ctoken" => prove_and_write(&parse_json_ctoken(input_file()?)?)?,
+        "prove_votes" => prove_and_write(&parse_json_votes(input_file()?)?)?,
+        "prove_vote" => prove_and_write(&parse_json_vote(input_file()?, read_passphrase)?)?,
+        "verify_native" => {
+            let proof_output: NativeProofOutput =
+                verify_input(input_file()?, &NATIVE_PROOF_ID, challenge_file)?;
+            if let Some(root) = proof_output.address_allowlist_root {
+                println!(
+                    "Address is in the address allowlist with root {}",
+                    prefix_hex::encode(root.to_vec())
+                );
+            }
+            if let Some(root) = proof_output.sanctions_root {
+                println!(
+                    "Address is not in the sanctions list with root {}",
+                    prefix_hex::encode(root.to_vec())
+                );
+            }
+            print_verified(&proof_output);
+        }
+        "verify_native_key" => print_verified(&verify_input::<NativeKeyProofOutput>(
+            input_file()?,
+            &NATIVE_KEY_PROOF_ID,
+            challenge_file,
+        )?),
+        "verify_aggregate" => print_verified(&verify_input::<AggregateProofOutput>(
+            input_file()?,
+            &AGGREGATE_PROOF_ID,
+            challenge_file,
+        )?),
+        "verify_contract" => {
+            let proof_output: ContractProofOutput =
+                verify_input(input_file()?, &CONTRACT_PROOF_ID, challenge_file)?;
+            let registry = load_registry()?;
+            let token = registry.check_balance_slot(
+                &proof_output.contract_address,
+                &proof_output.balance_slot,
+                proof_output.layout,
+            )?;
+            if let Some(token) = token {
+                token.check_blacklist(&proof_output.blacklist)?;
+            }
+            check_allowlist(&proof_output.code_hash)?;
+            if proof_output.not_frozen {
+                println!("Holder is not blacklisted by the token");
+            } else if proof_output.blacklist.is_some() {
+                println!("Holder is blacklisted by the token");
+            }
+            if let Some(root) = proof_output.address_allowlist_root {
+                println!(
+                    "Address is in the address allowlist with root {}",
+                    prefix_hex::encode(root.to_vec())
+                );
+            }
+            if let Some(root) = proof_output.sanctions_root {
+                println!(
+                    "Address is not in the sanctions list with root {}",
+                    prefix_hex::encode(root.to_vec())
+                );
+            }
+            print_verified(&proof_output);
+            if let Some(token) = token {
+                println!(
+                    "Balance of {}",
+                    token.format_balance_claim(&proof_output.balance_claim)
+                );
+            }
+        }
+        "verify_inactivity" => print_verified(&verify_input::<InactivityProofOutput>(
+            input_file()?,
+            &INACTIVITY_PROOF_ID,
+            challenge_file,
+        )?),
+        "verify_owner" => print_verified(&verify_input::<OwnerProofOutput>(
+            input_file()?,
+            &OWNER_PROOF_ID,
+            challenge_file,
+        )?),
+        "verify_usd" => {
+            let proof_output: UsdProofOutput =
+                verify_input(input_file()?, &USD_PROOF_ID, challenge_file)?;
+            if let Some(asset) = &proof_output.asset {
+                let registry = load_registry()?;
+                registry.check_balance_slot(&asset.address, &asset.balance_slot, asset.layout)?;
+            }
+            print_verified(&proof_output);
+        }
+        "verify_supply_share" => {
+            let proof_output: SupplyShareProofOutput =
+                verify_input(input_file()?, &SUPPLY_SHARE_PROOF_ID, challenge_file)?;
+            let registry = load_registry()?;
+            registry.check_balance_slot(
+                &proof_output.contract_address,
+                &proof_output.balance_slot,
+                proof_output.layout,
+            )?;
+            print_verified(&proof_output);
+        }
+        "verify_vault" => {
+            let proof_output: VaultProofOutput =
+                verify_input(input_file()?, &VAULT_PROOF_ID, challenge_file)?;
+            let registry = load_registry()?;
+            registry.check_balance_slot(
+                &proof_output.vault_address,
+                &proof_output.balance_slot,
+                proof_output.layout,
+            )?;
+            let underlying = match &proof_output.assets {
+                VaultAssets::Held(underlying) => registry.check_balance_slot(
+                    &underlying.address,
+                    &underlying.balance_slot,
+                    underlying.layout,
+                )?,
+                VaultAssets::Stored(_) => None,
+            };
+            print_verified(&proof_output);
+            if let Some(token) = underlying {
+                println!(
+                    "Shares worth at least {}",
+                    token.format_units(&proof_output.threshold)
+                );
+            }
+        }
+        "verify_steth" => {
+            let proof_output: StethProofOutput =
+                verify_input(input_file()?, &STETH_PROOF_ID, challenge_file)?;
+            if let Some(wrapped) = &proof_output.wrapped {
+                let registry = load_registry()?;
+                registry.check_balance_slot(
+                    &wrapped.address,
+                    &wrapped.balance_slot,
+                    wrapped.layout,
+                )?;
+            }
+            print_verified(&proof_output);
+        }
+        "verify_aave" => {
+            let proof_output: AaveProofOutput =
+                verify_input(input_file()?, &AAVE_PROOF_ID, challenge_file)?;
+            print_verified(&proof_output);
+            let registry = load_registry()?;
+            if let Some(token) = registry.find_by_address(&proof_output.asset_address) {
+                println!(
+                    "Supplied balance of at least {}",
+                    token.format_units(&proof_output.threshold)
+                );
+            }
+        }
+        "verify_lp" => {
+            let proof_output: LpProofOutput =
+                verify_input(input_file()?, &LP_PROOF_ID, challenge_file)?;
+            check_allowlist(&proof_output.code_hash)?;
+            print_verified(&proof_output);
+            let registry = load_registry()?;
+            for (token, threshold) in [
+                (&proof_output.token0, &proof_output.threshold0),
+                (&proof_output.token1, &proof_output.threshold1),
+            ] {
+                if let Some(token) = registry.find_by_address(token) {
+                    println!("Entitled to at least {}", token.format_units(threshold));
+                }
+            }
+        }
+        "verify_ctoken" => {
+            let proof_output: CTokenProofOutput =
+                verify_input(input_file()?, &CTOKEN_PROOF_ID, challenge_file)?;
+            let registry = load_registry()?;
+            let underlying = match &proof_output.underlying {
+                Some(underlying) => registry.check_balance_slot(
+                    &underlying.address,
+                    &underlying.balance_slot,
+                    underlying.layout,
+                )?,
+                None => None,
+            };
+            print_verified(&proof_output);
+            if let Some(token) = underlying {
+                println!(
+                    "cTokens worth at least {}",
+                    token.format_units(&proof_output.threshold)
+                );
+            }
+        }
+        "verify_votes" => {
+            let proof_output: VotesProofOutput =
+                verify_input(input_file()?, &VOTES_PROOF_ID, challenge_file)?;
+            check_allowlist(&proof_output.code_hash)?;
+            print_verified(&proof_output);
+            let registry = load_registry()?;
+            if let Some(token) = registry.find_by_address(&proof_output.token_address) {
+                println!(
+                    "Voting power of at least {}",
+                    token.format_units(&proof_output.threshold)
+                );
+            }
+        }
+        "verify_vote" => {
+            let proof_output: VoteProofOutput =
+                verify_input(input_file()?, &VOTE_PROOF_ID, challenge_file)?;
+            if let Some(asset) = &proof_output.asset {
+                let registry = load_registry()?;
+                registry.check_balance_slot(&asset.address, &asset.balance_slot, asset.layout)?;
+            }
+            print_verified(&proof_output);
+        }
+        "tally" => {
+            // the input is a directory of vote receipts
+            let tally = tally_directory(input_file()?)?;
+            if let Some(asset) = &tally.asset {
+                let registry = load_registry()?;
+                registry.check_balance_slot(&asset.address, &asset.balance_slot, asset.layout)?;
+            }
+            println!(
+                "Tallied {} votes for poll {} at snapshot block {}",
+                tally.counted,
+                prefix_hex::encode(tally.poll_id.to_vec()),
+                prefix_hex::encode(tally.block_hash.to_vec())
+            );
+            for (choice, total) in &tally.totals {
+                println!("Choice {}: {}", choice, total);
+            }
+            for nullifier in &tally.rejected {
+                println!(
+                    "Rejected a reused nullifier: {}",
+                    prefix_hex::encode(nullifier.to_vec())
+                );
+            }
+        }
+        "sign" => {
+            let input_file = input_file()?;
+            answer_challenge(input_file, challenge_file)?;
+            let keystore = matches
+                .get_one::<String>("keystore")
+                .ok_or("The sign command requires a --keystore file")?;
+            let private_key = load_keystore(keystore, &read_passphrase()?)?;
+            let user_address = sign_request_file(input_file, &private_key)?;
+            println!(
+                "Message signed by {} and written to {}",
+                user_address, input_file
+            );
+        }
+        "sign_mnemonic" => {
+            let input_file = input_file()?;
+            answer_challenge(input_file, challenge_file)?;
+            let account_count = sign_aggregate_request_file(input_file, &read_mnemonic()?)?;
+            println!(
+                "Message signed by {} funded accounts and written to {}",
+                account_count, input_file
+            );
+        }
+        "build_address_allowlist" => {
+            // the input is a CSV file whose first column holds the allowed addresses
+            let addresses = read_address_csv(input_file()?)?;
+            let tree = build_address_tree(&addresses)?;
 
-            write_json(&receipt, "./target/proofs").expect("Failed to write to file.");
+            let tree_file = "./target/trees/address_allowlist.json";
+            write_address_tree(&tree, tree_file)?;
             println!(
-                "STARK receipt successfully produced and committed to: {:x?}",
-                "./target/proofs/receipt.json"
+                "Address allowlist of {} addresses with root {} written to {}",
+                addresses.len(),
+                tree.root,
+                tree_file
             );
         }
-        "prove_contract" => {
-            let request = parse_json_contract(input_file)?;
-            let receipt = prove_assets(&request)?;
+        "build_sanctions_tree" => {
+            // the input is a plain text file with one sanctioned address per line
+            let addresses = read_address_list(input_file()?)?;
+            let tree = build_sorted_address_tree(&addresses)?;
 
-            write_json(&receipt, "./target/proofs").expect("Failed to write to file.");
+            let tree_file = "./target/trees/sanctions.json";
+            write_address_tree(&tree, tree_file)?;
             println!(
-                "STARK receipt successfully produced and committed to: {:x?}",
-                "./target/proofs/receipt.json"
+                "Sanctions tree of {} leaves (with 2 sentinels) with root {} written to {}",
+                tree.leaves.len(),
+                tree.root,
+                tree_file
             );
         }
-        "verify_native" => {
-            let receipt = parse_json_receipt(input_file)?;
-            let proof_output: NativeProofOutput = verify_receipt(&receipt, &NATIVE_PROOF_ID)?;
-            println!("Verified proof successfully!");
-            println!("{:x?}", &proof_output);
+        "challenge" => {
+            let challenge_file =
+                challenge_file.ok_or("The challenge command requires a --challenge file")?;
+            let verifier = matches
+                .get_one::<String>("verifier")
+                .ok_or("The challenge command requires a --verifier identity")?;
+            let validity = *matches.get_one::<u64>("validity").unwrap();
+            let challenge = Challenge::new(verifier, validity)?;
+
+            write_challenge(&challenge, challenge_file)?;
+            println!(
+                "Challenge for {} written to {}, valid until {}",
+                verifier, challenge_file, challenge.expires_at
+            );
         }
-        "verify_contract" => {
-            let receipt = parse_json_receipt(input_file)?;
-            let proof_output: ContractProofOutput = verify_receipt(&receipt, &CONTRACT_PROOF_ID)?;
-            println!("Verified proof successfully!");
-            println!("{:x?}", &proof_output);
+        "reveal" => {
+            let receipt = parse_json_receipt(input_file()?)?;
+            let address = matches
+                .get_one::<String>("address")
+                .ok_or("The reveal command requires an --address")?;
+            let salt = matches
+                .get_one::<String>("salt")
+                .ok_or("The reveal command requires a --salt")?;
+
+            if reveal_address(
+                &receipt,
+                &prefix_hex::decode(address)?,
+                &prefix_hex::decode(salt)?,
+            )? {
+                println!("Receipt belongs to address {}", address);
+            } else {
+                return Err(format!("Receipt does not belong to address {}", address).into());
+            }
+        }
+        "open_balance" => {
+            let receipt = parse_json_receipt(input_file()?)?;
+            let balance = matches
+                .get_one::<String>("balance")
+                .ok_or("The open_balance command requires a --balance")?;
+            let blinding = matches
+                .get_one::<String>("blinding")
+                .ok_or("The open_balance command requires a --blinding")?;
+
+            let mut balance_bytes = [0_u8; 32];
+            U256::from_dec_str(balance)
+                .map_err(|_| "Invalid --balance")?
+                .to_big_endian(&mut balance_bytes);
+            if open_balance(&receipt, &balance_bytes, &prefix_hex::decode(blinding)?)? {
+                println!("Receipt commits to an exact balance of {}", balance);
+            } else {
+                return Err(format!("Receipt does not commit to a balance of {}", balance).into());
+            }
         }
         _ => {
             eprintln!("Invalid command. Please use 'prove_eth' or 'prove_erc'.");
@@ -78,6 +520,58 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+// Proves a request and writes its receipt to ./target/proofs
+fn prove_and_write<T: Request>(request: &T) -> Result<(), Box<dyn std::error::Error>> {
+    let receipt = prove_assets(request)?;
+
+    write_json(&receipt, "./target/proofs").expect("Failed to write to file.");
+    println!(
+        "STARK receipt successfully produced and committed to: {:x?}",
+        "./target/proofs/receipt.json"
+    );
+
+    Ok(())
+}
+
+// Verifies the receipt of the input file against a guest's image ID, and its committed message or
+// context against the challenge if one was provided. Proofs that commit neither fail a challenge.
+fn verify_input<T: ProofOutput>(
+    input_file: &str,
+    image_id: &[u32; 8],
+    challenge_file: Option<&String>,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let receipt = parse_json_receipt(input_file)?;
+    let proof_output: T = verify_receipt(&receipt, image_id)?;
+    if let Some(challenge_file) = challenge_file {
+        let challenge = read_challenge(challenge_file)?;
+        match (proof_output.message(), proof_output.context()) {
+            (Some(message), _) => challenge.check_message(message)?,
+            (None, Some(context)) => challenge.check_context(context)?,
+            (None, None) => return Err("This proof commits nothing to answer a challenge".into()),
+        }
+    }
+
+    Ok(proof_output)
+}
+
+fn print_verified<T: Debug>(proof_output: &T) {
+    println!("Verified proof successfully!");
+    println!("{:x?}", proof_output);
+}
+
+// Replaces the request file's message with the challenge message, if a challenge was provided
+fn answer_challenge(
+    input_file: &str,
+    challenge_file: Option<&String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(challenge_file) = challenge_file {
+        let message = read_challenge(challenge_file)?.to_message();
+        update_json_fields(input_file, &[("message", serde_json::json!(message))])?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
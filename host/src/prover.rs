@@ -3,25 +3,17 @@ use crate::ethereum::requests::Request;
 use proof_core::proof_io::ProofInput;
 use risc0_zkvm::{serde::to_vec, Prover, Receipt};
 
-use proof_core::eth_utils::check_signature;
-
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 pub fn prove_assets<T: Request>(request: &T) -> Result<Receipt> {
-    // Check that the provided signature matches the account before running the costly proving algorithm
-    assert!(check_signature(
-        &request.get_signature(),
-        &request.get_message(),
-        &request.get_user_address()
-    )?);
-    println!(
-        "Signature corresponds to address {}",
-        &request.get_user_address()
-    );
+    // Check that the request proves ownership of the account before running the costly proving algorithm
+    assert!(request.check_ownership()?);
+    let user_addresses = request.get_user_addresses().join(", ");
+    println!("Ownership verified for address {}", &user_addresses);
     println!(
         "Requesting {} for {}",
         request.get_description(),
-        request.get_user_address()
+        &user_addresses
     );
 
     // get_input queries the ETHEREUM_PROVIDER over HTTP for a state root and account proof for "address"
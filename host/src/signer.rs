@@ -0,0 +1,136 @@
+use crate::ethereum::rpc::has_balance;
+use crate::file_utils::{read_json_file, update_json_fields, vote_text};
+use crate::hd_wallet::derive_private_keys;
+use proof_core::eth_utils::{
+    check_signature, format_eth_message, private_key_to_address, sign_message,
+};
+use serde_json::json;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Signs the request file's message with the given key, and writes the signature and the
+// corresponding address back into the request file. Returns the signing address.
+pub fn sign_request_file(filename: &str, private_key: &[u8; 32]) -> Result<String> {
+    let data = read_json_file(filename)?;
+    // Vote requests sign the vote message of their poll and choice
+    let vote = vote_text(&data)?;
+    let message = match &vote {
+        Some(vote) => vote.as_str(),
+        None => data["message"]
+            .as_str()
+            .ok_or("Request file has no message to sign")?,
+    };
+
+    let user_address = prefix_hex::encode(private_key_to_address(private_key)?.to_vec());
+    let formatted_message = format_eth_message(String::from(message));
+    let signature = prefix_hex::encode(sign_message(private_key, formatted_message.as_bytes())?);
+
+    // Make sure the signature will be accepted by the prover before writing it
+    if !check_signature(&signature, &formatted_message, &user_address)? {
+        return Err("Produced signature does not match the keystore address".into());
+    }
+
+    update_json_fields(
+        filename,
+        &[
+            ("user_address", json!(user_address)),
+            ("signature", json!(signature)),
+            ("message", json!(message)),
+        ],
+    )?;
+
+    Ok(user_address)
+}
+
+// Derives every key of the request file's derivation path range from the mnemonic, and signs the
+// message with each funded account. The signed accounts are written back into the request file,
+// which is then ready for prove_aggregate. Returns the number of signed accounts.
+pub fn sign_aggregate_request_file(filename: &str, mnemonic: &str) -> Result<usize> {
+    let data = read_json_file(filename)?;
+    let field = |key: &str| {
+        data[key]
+            .as_str()
+            .ok_or(format!("Request file has no {} field", key))
+    };
+    let provider = field("provider")?;
+    let block_number = field("block_number")?;
+    let derivation_path = field("derivation_path")?;
+    let formatted_message = format_eth_message(String::from(field("message")?));
+
+    let mut accounts = Vec::new();
+    for private_key in derive_private_keys(mnemonic, derivation_path)? {
+        let user_address = prefix_hex::encode(private_key_to_address(&private_key)?.to_vec());
+        // Empty accounts add nothing to the total and only make the proof more expensive
+        if !has_balance(provider, &user_address, block_number)? {
+            continue;
+        }
+
+        let signature =
+            prefix_hex::encode(sign_message(&private_key, formatted_message.as_bytes())?);
+        if !check_signature(&signature, &formatted_message, &user_address)? {
+            return Err("Produced signature does not match the derived address".into());
+        }
+        accounts.push(json!({ "user_address": user_address, "signature": signature }));
+    }
+
+    let account_count = accounts.len();
+    update_json_fields(filename, &[("accounts", json!(accounts))])?;
+
+    Ok(account_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_sign_request_file() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("test_sign.json");
+        let mut file = File::create(&file_path).unwrap();
+        file.write_all(br#"{ "message": "hello world", "expected_balance": 0 }"#)
+            .unwrap();
+
+        let private_key = hex!("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let file_path = file_path.to_str().unwrap();
+        let user_address = sign_request_file(file_path, &private_key).unwrap();
+        let contents = read_json_file(file_path).unwrap();
+
+        assert_eq!(user_address, "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+        assert_eq!(contents["user_address"], user_address.as_str());
+        assert_eq!(contents["expected_balance"], 0);
+        assert!(check_signature(
+            contents["signature"].as_str().unwrap(),
+            &format_eth_message("hello world".to_string()),
+            &user_address
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_sign_vote_request_file() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("test_sign_vote.json");
+        let mut file = File::create(&file_path).unwrap();
+        file.write_all(br#"{ "message": "hello world", "poll": "treasury-2024", "choice": 1 }"#)
+            .unwrap();
+
+        let private_key = hex!("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let file_path = file_path.to_str().unwrap();
+        let user_address = sign_request_file(file_path, &private_key).unwrap();
+        let contents = read_json_file(file_path).unwrap();
+        let vote = vote_text(&contents).unwrap().unwrap();
+
+        assert_eq!(contents["message"], vote.as_str());
+        assert!(check_signature(
+            contents["signature"].as_str().unwrap(),
+            &format_eth_message(vote),
+            &user_address
+        )
+        .unwrap());
+    }
+}
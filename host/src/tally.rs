@@ -0,0 +1,173 @@
+use crate::file_utils::parse_json_receipt;
+use crate::verifier::verify_receipt;
+use methods::VOTE_PROOF_ID;
+use primitive_types::U256;
+use proof_core::proof_io::{NullifierKind, ProvenBalance, TokenSlot, VoteProofOutput};
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::read_dir;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Outcome of a poll
+#[derive(Debug, PartialEq)]
+pub struct Tally {
+    pub poll_id: [u8; 32],
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub asset: Option<TokenSlot>,
+    pub nullifier_kind: NullifierKind,
+    // total weight of each choice
+    pub totals: BTreeMap<u32, U256>,
+    pub counted: usize,
+    // nullifiers of rejected votes, each cast by an address that had already voted
+    pub rejected: Vec<[u8; 32]>,
+}
+
+// Verifies every vote receipt (.json file) of a directory, then tallies them in file name order
+pub fn tally_directory(directory: &str) -> Result<Tally> {
+    let mut paths = Vec::new();
+    for entry in read_dir(directory)? {
+        let path = entry?.path();
+        if path.extension().and_then(|extension| extension.to_str()) == Some("json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut votes = Vec::new();
+    for path in paths {
+        let receipt = parse_json_receipt(path.to_str().ok_or("Invalid receipt path")?)?;
+        votes.push(verify_receipt(&receipt, &VOTE_PROOF_ID)?);
+    }
+
+    tally(&votes)
+}
+
+// Sums the weight of each choice. Every vote must be for the same poll, at the same snapshot (block
+// hash and state root) and weighted by the same asset, read at the same balance slot and layout of
+// the same code. Only the first vote of a nullifier counts, so that an address can neither vote
+// twice nor change its vote, and every nullifier must be of the same kind, as a voter would have one
+// of each.
+pub fn tally(votes: &[VoteProofOutput]) -> Result<Tally> {
+    let first = votes.first().ok_or("No votes to tally")?;
+    let mut result = Tally {
+        poll_id: first.poll_id,
+        root: first.root,
+        block_hash: first.block_hash,
+        asset: first.asset.clone(),
+        nullifier_kind: first.nullifier_kind,
+        totals: BTreeMap::new(),
+        counted: 0,
+        rejected: Vec::new(),
+    };
+
+    let mut nullifiers = HashSet::new();
+    for vote in votes {
+        if vote.poll_id != result.poll_id
+            || vote.root != result.root
+            || vote.block_hash != result.block_hash
+            || vote.asset != result.asset
+        {
+            return Err("Votes are not for the same poll, snapshot and asset".into());
+        }
+        if vote.nullifier_kind != result.nullifier_kind {
+            return Err("Votes mix key and address nullifiers".into());
+        }
+        if !nullifiers.insert(vote.nullifier) {
+            result.rejected.push(vote.nullifier);
+            continue;
+        }
+
+        let weight = match &vote.weight {
+            ProvenBalance::Equal(weight) | ProvenBalance::Tier { tier: weight, .. } => weight,
+            _ => return Err("Vote weight must be a balance or a tier".into()),
+        };
+        let total = result.totals.entry(vote.choice).or_insert_with(U256::zero);
+        *total = total
+            .checked_add(U256::from_big_endian(weight))
+            .ok_or("Total weight overflows")?;
+        result.counted += 1;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proof_core::eth_utils::SlotLayout;
+
+    fn vote(choice: u32, weight: u64, nullifier: u8) -> VoteProofOutput {
+        let mut weight_bytes = [0_u8; 32];
+        U256::from(weight).to_big_endian(&mut weight_bytes);
+
+        VoteProofOutput {
+            root: [1_u8; 32],
+            block_hash: [2_u8; 32],
+            asset: None,
+            poll_id: [3_u8; 32],
+            choice,
+            weight: ProvenBalance::Equal(weight_bytes),
+            nullifier: [nullifier; 32],
+            nullifier_kind: NullifierKind::PrivateKey,
+        }
+    }
+
+    #[test]
+    fn test_tally() {
+        let mut tiered = vote(1, 0, 4);
+        let mut tier = [0_u8; 32];
+        tier[31] = 50;
+        tiered.weight = ProvenBalance::Tier {
+            tier,
+            tiers: vec![[0_u8; 32], tier],
+        };
+        // the second vote of nullifier 1 is rejected, whatever its choice
+        let votes = vec![
+            vote(0, 100, 1),
+            vote(1, 30, 2),
+            vote(1, 999, 1),
+            vote(0, 5, 3),
+            tiered,
+        ];
+        let result = tally(&votes).unwrap();
+
+        assert_eq!(result.counted, 4);
+        assert_eq!(result.rejected, vec![[1_u8; 32]]);
+        assert_eq!(result.totals[&0], U256::from(105));
+        assert_eq!(result.totals[&1], U256::from(80));
+    }
+
+    #[test]
+    fn test_tally_mixed_polls() {
+        let mut other_poll = vote(0, 1, 2);
+        other_poll.poll_id = [9_u8; 32];
+
+        assert!(tally(&[vote(0, 1, 1), other_poll]).is_err());
+        let mut other_root = vote(0, 1, 2);
+        other_root.root = [9_u8; 32];
+        assert!(tally(&[vote(0, 1, 1), other_root]).is_err());
+        // a signed vote of a key that also voted with it
+        let mut signed = vote(0, 1, 2);
+        signed.nullifier_kind = NullifierKind::Address;
+        assert!(tally(&[vote(0, 1, 1), signed]).is_err());
+
+        // the same token, read at another balance slot
+        let token = |balance_slot: u8| TokenSlot {
+            address: [4_u8; 20],
+            code_hash: [5_u8; 32],
+            balance_slot: [balance_slot; 32],
+            layout: SlotLayout::Solidity,
+        };
+        let mut first = vote(0, 1, 1);
+        first.asset = Some(token(0));
+        let mut other_slot = vote(0, 1, 2);
+        other_slot.asset = Some(token(1));
+        assert!(tally(&[first.clone(), other_slot]).is_err());
+        let mut same_slot = vote(0, 1, 2);
+        same_slot.asset = Some(token(0));
+        assert!(tally(&[first, same_slot]).is_ok());
+        assert!(tally(&[]).is_err());
+    }
+}
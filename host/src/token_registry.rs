@@ -0,0 +1,318 @@
+use primitive_types::U256;
+use proof_core::{
+    eth_utils::SlotLayout,
+    proof_io::{BlacklistLayout, ProvenBalance},
+};
+use serde::Deserialize;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Registry of common tokens bundled with the binary, used unless another registry file is given
+const DEFAULT_REGISTRY: &str = include_str!("../tokens.json");
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct TokenInfo {
+    // filled in from the registry key
+    #[serde(skip)]
+    pub symbol: String,
+    pub address: String,
+    // slot of the balances mapping
+    pub balance_slot: u64,
+    // slot of the total supply, if the token stores it
+    pub total_supply_slot: Option<u64>,
+    pub layout: SlotLayout,
+    pub decimals: u8,
+    // where the token records frozen accounts, if it can freeze them
+    pub blacklist: Option<TokenBlacklist>,
+}
+
+// Registry form of a BlacklistLayout, with a numbered slot
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenBlacklist {
+    Mapping(u64),
+    BalanceHighBit,
+}
+
+// Maps chain ID, then symbol, to a token
+#[derive(Debug)]
+pub struct TokenRegistry(HashMap<String, HashMap<String, TokenInfo>>);
+
+impl TokenRegistry {
+    pub fn load(filename: Option<&str>) -> Result<Self> {
+        match filename {
+            Some(filename) => {
+                let mut file = File::open(filename)?;
+                let mut contents = String::new();
+                file.read_to_string(&mut contents)?;
+                Self::from_json(&contents)
+            }
+            None => Self::from_json(DEFAULT_REGISTRY),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut chains: HashMap<String, HashMap<String, TokenInfo>> = serde_json::from_str(json)?;
+        for tokens in chains.values_mut() {
+            for (symbol, token) in tokens.iter_mut() {
+                token.symbol = symbol.clone();
+            }
+        }
+
+        Ok(TokenRegistry(chains))
+    }
+
+    pub fn get(&self, chain_id: u64, symbol: &str) -> Result<&TokenInfo> {
+        self.0
+            .get(&chain_id.to_string())
+            .and_then(|tokens| tokens.get(symbol))
+            .ok_or_else(|| format!("Unknown token {} on chain {}", symbol, chain_id).into())
+    }
+
+    // Reverse lookup used to print decoded journals, which only contain the contract address
+    pub fn find_by_address(&self, address: &[u8; 20]) -> Option<&TokenInfo> {
+        let address = prefix_hex::encode(address.to_vec());
+        self.0
+            .values()
+            .flat_map(HashMap::values)
+            .find(|token| token.address.to_lowercase() == address)
+    }
+
+    // Looks up the token whose balance a proof read, and fails if the registry knows it keeps its
+    // balances at another slot or with another layout than the proof committed
+    pub fn check_balance_slot(
+        &self,
+        address: &[u8; 20],
+        balance_slot: &[u8; 32],
+        layout: SlotLayout,
+    ) -> Result<Option<&TokenInfo>> {
+        let token = match self.find_by_address(address) {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+        if *balance_slot != slot_word(token.balance_slot) || layout != token.layout {
+            return Err(format!(
+                "Proof did not read {} balances at the registered slot and layout",
+                token.symbol
+            )
+            .into());
+        }
+
+        Ok(Some(token))
+    }
+}
+
+fn slot_word(slot: u64) -> [u8; 32] {
+    let mut word = [0_u8; 32];
+    word[24..].copy_from_slice(&slot.to_be_bytes());
+    word
+}
+
+impl TokenInfo {
+    // Balance slot padded to bytes32, as expected in contract requests
+    pub fn balance_slot_hex(&self) -> String {
+        format!("0x{:064x}", self.balance_slot)
+    }
+
+    // Total supply slot padded to bytes32
+    pub fn total_supply_slot_hex(&self) -> Option<String> {
+        self.total_supply_slot
+            .map(|slot| format!("0x{:064x}", slot))
+    }
+
+    pub fn blacklist_layout(&self) -> Option<BlacklistLayout> {
+        self.blacklist.map(|blacklist| match blacklist {
+            TokenBlacklist::Mapping(slot) => BlacklistLayout::Mapping(slot_word(slot)),
+            TokenBlacklist::BalanceHighBit => BlacklistLayout::BalanceHighBit,
+        })
+    }
+
+    // Fails unless a proof read frozen accounts where the token records them, which for a flag
+    // packed in the balance slot also ensures that it was masked off the balance
+    pub fn check_blacklist(&self, blacklist: &Option<BlacklistLayout>) -> Result<()> {
+        if *blacklist != self.blacklist_layout() {
+            return Err(format!(
+                "Proof did not read frozen {} accounts at the registered blacklist",
+                self.symbol
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    // Parses an amount in human units, e.g. "1500.25" or "1500.25 USDC", into base units
+    pub fn parse_units(&self, amount: &str) -> Option<U256> {
+        let amount = amount.trim();
+        let amount = amount
+            .strip_suffix(self.symbol.as_str())
+            .unwrap_or(amount)
+            .trim_end();
+        let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || fraction.len() > self.decimals as usize
+            || !(integer.chars().chain(fraction.chars())).all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let digits = format!(
+            "{}{:0<width$}",
+            integer,
+            fraction,
+            width = self.decimals as usize
+        );
+        U256::from_dec_str(&digits).ok()
+    }
+
+    // Formats an amount in base units as human units, e.g. "1500.25 USDC"
+    pub fn format_units(&self, amount: &[u8; 32]) -> String {
+        let digits = U256::from_big_endian(amount).to_string();
+        let decimals = self.decimals as usize;
+        let digits = format!("{:0>width$}", digits, width = decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            format!("{} {}", integer, self.symbol)
+        } else {
+            format!("{}.{} {}", integer, fraction, self.symbol)
+        }
+    }
+
+    // Describes a committed balance claim in human units
+    pub fn format_balance_claim(&self, claim: &ProvenBalance) -> String {
+        match claim {
+            ProvenBalance::AtLeast(amount) => format!("at least {}", self.format_units(amount)),
+            ProvenBalance::AtMost(amount) => format!("at most {}", self.format_units(amount)),
+            ProvenBalance::Equal(amount) => format!("exactly {}", self.format_units(amount)),
+            ProvenBalance::Range(min, max) => format!(
+                "between {} and {}",
+                self.format_units(min),
+                self.format_units(max)
+            ),
+            ProvenBalance::Tier { tier, .. } => format!("at least {}", self.format_units(tier)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usdc() -> TokenInfo {
+        TokenRegistry::load(None)
+            .unwrap()
+            .get(1, "USDC")
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_default_registry() {
+        let registry = TokenRegistry::load(None).unwrap();
+        let usdc = registry.get(1, "USDC").unwrap();
+
+        assert_eq!(usdc.symbol, "USDC");
+        assert_eq!(usdc.decimals, 6);
+        assert_eq!(usdc.layout, SlotLayout::Solidity);
+        assert_eq!(
+            usdc.balance_slot_hex(),
+            "0x0000000000000000000000000000000000000000000000000000000000000009"
+        );
+        assert_eq!(
+            usdc.total_supply_slot_hex().unwrap(),
+            "0x000000000000000000000000000000000000000000000000000000000000000b"
+        );
+        assert_eq!(
+            usdc.blacklist_layout(),
+            Some(BlacklistLayout::BalanceHighBit)
+        );
+        let mut usdt_blacklist_slot = [0_u8; 32];
+        usdt_blacklist_slot[31] = 6;
+        assert_eq!(
+            registry.get(1, "USDT").unwrap().blacklist_layout(),
+            Some(BlacklistLayout::Mapping(usdt_blacklist_slot))
+        );
+        assert_eq!(registry.get(1, "DAI").unwrap().blacklist_layout(), None);
+        assert!(usdc
+            .check_blacklist(&Some(BlacklistLayout::BalanceHighBit))
+            .is_ok());
+        assert!(usdc.check_blacklist(&None).is_err());
+        assert!(registry.get(5, "USDC").is_err());
+        assert_eq!(
+            registry
+                .find_by_address(&prefix_hex::decode(&usdc.address).unwrap())
+                .unwrap()
+                .symbol,
+            "USDC"
+        );
+    }
+
+    #[test]
+    fn test_check_balance_slot() {
+        let registry = TokenRegistry::load(None).unwrap();
+        let usdc = usdc();
+        let address = prefix_hex::decode(&usdc.address).unwrap();
+
+        let token = registry
+            .check_balance_slot(&address, &slot_word(9), SlotLayout::Solidity)
+            .unwrap();
+        assert_eq!(token, Some(&usdc));
+        assert!(registry
+            .check_balance_slot(&address, &slot_word(9), SlotLayout::Vyper)
+            .is_err());
+        assert!(registry
+            .check_balance_slot(&address, &slot_word(3), SlotLayout::Solidity)
+            .is_err());
+        assert_eq!(
+            registry
+                .check_balance_slot(&[0_u8; 20], &slot_word(3), SlotLayout::Vyper)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_units() {
+        let usdc = usdc();
+
+        assert_eq!(
+            usdc.parse_units("1500.25"),
+            Some(U256::from(1_500_250_000_u64))
+        );
+        assert_eq!(
+            usdc.parse_units("1500.25 USDC"),
+            Some(U256::from(1_500_250_000_u64))
+        );
+        assert_eq!(
+            usdc.parse_units("1500"),
+            Some(U256::from(1_500_000_000_u64))
+        );
+        assert_eq!(usdc.parse_units(".5"), Some(U256::from(500_000_u64)));
+        assert_eq!(usdc.parse_units("0.0000001"), None);
+        assert_eq!(usdc.parse_units("1500.25 DAI"), None);
+        assert_eq!(usdc.parse_units("-1"), None);
+        assert_eq!(usdc.parse_units("."), None);
+    }
+
+    #[test]
+    fn test_format_units() {
+        let usdc = usdc();
+        let mut amount = [0_u8; 32];
+
+        U256::from(1_500_250_000_u64).to_big_endian(&mut amount);
+        assert_eq!(usdc.format_units(&amount), "1500.25 USDC");
+        U256::from(7_u64).to_big_endian(&mut amount);
+        assert_eq!(usdc.format_units(&amount), "0.000007 USDC");
+        assert_eq!(usdc.format_units(&[0_u8; 32]), "0 USDC");
+        assert_eq!(
+            usdc.format_balance_claim(&ProvenBalance::AtMost([0_u8; 32])),
+            "at most 0 USDC"
+        );
+    }
+}
@@ -1,7 +1,17 @@
-use proof_core::proof_io::ProofOutput;
+use methods::{CONTRACT_PROOF_ID, NATIVE_PROOF_ID};
+use proof_core::{
+    eth_utils::{address_commitment, balance_commitment},
+    proof_io::{ContractProofOutput, NativeProofOutput, ProofOutput},
+};
 pub use risc0_zkvm::{serde::from_slice, Receipt};
+use serde_json::Value;
+
+use std::fs::File;
+use std::io::Read;
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+// (address, balance) commitments of a receipt, each only present if the prover asked for it
+type Commitments = (Option<[u8; 32]>, Option<[u8; 32]>);
 
 pub fn verify_receipt<T: ProofOutput>(receipt: &Receipt, image_id: &[u32; 8]) -> Result<T> {
     // Verify receipt seal
@@ -12,3 +22,91 @@ pub fn verify_receipt<T: ProofOutput>(receipt: &Receipt, image_id: &[u32; 8]) ->
 
     Ok(from_slice(journal)?)
 }
+
+// Checks that a native or contract receipt commits to the given address and salt
+pub fn reveal_address(receipt: &Receipt, address: &[u8; 20], salt: &[u8; 32]) -> Result<bool> {
+    let (committed, _) = get_commitments(receipt)?;
+    let committed = committed.ok_or("Receipt does not contain an address commitment")?;
+
+    Ok(committed == address_commitment(address, salt))
+}
+
+// Opens the balance commitment of a native or contract receipt offline, without re-proving
+pub fn open_balance(receipt: &Receipt, balance: &[u8; 32], blinding: &[u8; 32]) -> Result<bool> {
+    let (_, committed) = get_commitments(receipt)?;
+    let committed = committed.ok_or("Receipt does not contain a balance commitment")?;
+
+    Ok(committed == balance_commitment(balance, blinding))
+}
+
+// Looks up a contract's code hash in a code hash allowlist file mapping token names to expected code hashes,
+// e.g. { "USDC": "0x..." }. Returns the matching token name, or fails if none matches (or if
+// `token` is given and its expected code hash differs).
+pub fn check_code_hash(
+    allowlist_file: &str,
+    code_hash: &[u8; 32],
+    token: Option<&str>,
+) -> Result<String> {
+    let mut file = File::open(allowlist_file)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    match_code_hash(&serde_json::from_str(&contents)?, code_hash, token)
+}
+
+fn match_code_hash(allowlist: &Value, code_hash: &[u8; 32], token: Option<&str>) -> Result<String> {
+    let allowlist = allowlist
+        .as_object()
+        .ok_or("Allowlist must map token names to code hashes")?;
+    let code_hash = prefix_hex::encode(code_hash.to_vec());
+
+    for (name, expected) in allowlist {
+        if (token.is_none() || token == Some(name.as_str()))
+            && expected.as_str().map(str::to_lowercase).as_deref() == Some(code_hash.as_str())
+        {
+            return Ok(name.clone());
+        }
+    }
+
+    Err(format!(
+        "Contract code hash {} is not in the code hash allowlist",
+        code_hash
+    )
+    .into())
+}
+
+// Returns the (address, balance) commitments of a verified native or contract receipt
+fn get_commitments(receipt: &Receipt) -> Result<Commitments> {
+    match verify_receipt::<NativeProofOutput>(receipt, &NATIVE_PROOF_ID) {
+        Ok(output) => Ok((output.address_commitment, output.balance_commitment)),
+        Err(_) => {
+            let output: ContractProofOutput = verify_receipt(receipt, &CONTRACT_PROOF_ID)?;
+            Ok((output.address_commitment, output.balance_commitment))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_match_code_hash() {
+        let allowlist = json!({
+            "USDC": format!("0x{}", "AB".repeat(32)),
+            "DAI": format!("0x{}", "cd".repeat(32)),
+        });
+
+        assert_eq!(
+            match_code_hash(&allowlist, &[0xab; 32], None).unwrap(),
+            "USDC"
+        );
+        assert_eq!(
+            match_code_hash(&allowlist, &[0xcd; 32], Some("DAI")).unwrap(),
+            "DAI"
+        );
+        assert!(match_code_hash(&allowlist, &[0xcd; 32], Some("USDC")).is_err());
+        assert!(match_code_hash(&allowlist, &[0xef; 32], None).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::{mapping_slot, packed_field, slot_offset, to_word, SlotLayout},
+    proof_io::{AaveProofInput, AaveProofOutput, BalanceClaim},
+    proof_utils::{check_balance_claim, ray_mul_down, slot_to_address, verify_account, verify_contract_storage, verify_signed_message, verify_storage_value},
+};
+use risc0_zkvm::guest::env;
+use sha3::{Digest, Keccak256};
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let AaveProofInput { pool_address, reserves_slot, asset_address, a_token_address, user_state_slot, user_address, root, block_hash, a_token_account_proof, balance_proof, pool_account_proof, liquidity_index_proof, last_update_proof, a_token_slot_proof, threshold, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Verify the holder's scaled balance, the low 128 bits of their UserState
+    let slot = mapping_slot(SlotLayout::Solidity, &user_address, &user_state_slot);
+    let (_, user_state) = verify_contract_storage(&root, &a_token_address, a_token_account_proof, &slot, balance_proof);
+    let scaled_balance = packed_field(&user_state, 0, 16);
+
+    // Verify the reserve data of the asset under the same state root:
+    // slot + 1 packs the uint128 liquidity index, slot + 3 the uint40 last update timestamp above a
+    // uint128 rate, and slot + 4 holds the reserve's aToken address
+    let pool = verify_account(&root, &pool_address, pool_account_proof);
+    let reserve_slot = mapping_slot(SlotLayout::Solidity, &asset_address, &reserves_slot);
+    let read = |offset: u64, proof| verify_storage_value(&pool.storage_root, &Keccak256::digest(slot_offset(&reserve_slot, offset)), proof);
+    let liquidity_index = to_word(packed_field(&read(1, liquidity_index_proof), 0, 16));
+    let last_update_timestamp = packed_field(&read(3, last_update_proof), 16, 5).as_u64();
+    if slot_to_address(&read(4, a_token_slot_proof)) != a_token_address {
+        panic!("aToken is not the reserve's aToken.");
+    }
+
+    // The stored index is at most the current normalized income, which only grows, so the balance
+    // is never overstated
+    let balance = ray_mul_down(&to_word(scaled_balance), &liquidity_index);
+    check_balance_claim(&balance, BalanceClaim::AtLeast(threshold));
+
+    env::commit(
+        &(AaveProofOutput {
+            pool_address,
+            reserves_slot,
+            asset_address,
+            a_token_address,
+            user_state_slot,
+            liquidity_index,
+            last_update_timestamp,
+            root,
+            block_hash,
+            threshold,
+            message,
+        }),
+    );
+}
@@ -0,0 +1,47 @@
+#![no_main]
+
+use primitive_types::U256;
+use proof_core::{
+    eth_utils::to_word,
+    proof_io::{AccountOwnership, AggregateProofInput, AggregateProofOutput},
+    proof_utils::{check_balance_claim, verify_account, verify_signed_message},
+};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let AggregateProofInput {root, block_hash, accounts, balance_claim, message} = env::read();
+
+    let mut total_balance = U256::zero();
+    let mut previous_address: Option<[u8; 20]> = None;
+
+    for AccountOwnership { user_address, account_proof, signature } in accounts {
+        // Addresses must be strictly increasing so that no account is counted twice
+        if matches!(previous_address, Some(previous) if user_address <= previous) {
+            panic!("Accounts must be sorted by address and unique.");
+        }
+        previous_address = Some(user_address);
+
+        // Verify signed message corresponds to provided address
+        // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+        // as soon as those are made available
+        verify_signed_message(&signature, &message, &user_address);
+
+        // Verify Merkle-Patricia trie proof (accountProof in eth_getProof), an absent account adds nothing
+        let balance = verify_account(&root, &user_address, account_proof).balance;
+        total_balance += U256::from_big_endian(&balance);
+    }
+
+    // Check the total balance against the claim (bound, range or tiers), the resulting claim is committed
+    let balance_claim = check_balance_claim(&to_word(total_balance), balance_claim);
+
+    env::commit(
+        &(AggregateProofOutput {
+            root,
+            block_hash,
+            balance_claim,
+            message,
+        }),
+    );
+}
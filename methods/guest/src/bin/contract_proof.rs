@@ -1,11 +1,10 @@
 #![no_main]
 
-use concat_arrays::concat_arrays;
-use eth_trie::Trie;
 use proof_core::{
-    eth_utils::be_bytes_geq,
-    proof_io::{ContractProofInput, ContractProofOutput},
-    proof_utils::{create_eth_trie, verify_signed_message},
+    eth_utils::{address_commitment, balance_commitment, mapping_slot},
+    merkle::{address_leaf, verify_merkle_proof, verify_non_membership},
+    proof_io::{BlacklistLayout, BlacklistProof, ContractProofInput, ContractProofOutput},
+    proof_utils::{check_balance_claim, split_balance_high_bit, verify_contract_storage, verify_signed_message, verify_storage_value},
 };
 use risc0_zkvm::guest::env;
 use sha3::{Digest, Keccak256};
@@ -13,37 +12,70 @@ use sha3::{Digest, Keccak256};
 risc0_zkvm::guest::entry!(main);
 
 pub fn main() {
-    let ContractProofInput { contract_address, balance_slot, user_address, storage_hash, block_hash, storage_proof, expected_balance, signature, message } = env::read();
+    let ContractProofInput { contract_address, balance_slot, layout, user_address, root, block_hash, account_proof, storage_proof, balance_claim, signature, message, address_salt, balance_blinding, blacklist, address_allowlist, sanctions } = env::read();
     
     // Verify signed message corresponds to provided address
     // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
     // as soon as those are made available
     verify_signed_message(&signature, &message, &user_address);
 
-    // Compute storage key: for balance mapping, it's Keccak(abi.encode(mapping_key, uint256(mapping_slot)))
-    let key_prehash: [u8; 64] = concat_arrays!([0_u8; 12], user_address, balance_slot);
-    let key_prehash = Keccak256::digest(&key_prehash);
-    let key = Keccak256::digest(&key_prehash).to_vec();
-    // Verify Merkle-Patricia trie proof (accountProof in eth_getProof)
-    let trie = create_eth_trie();
-    // Slot contents is automatically decoded and should contain balance
-    let balance = trie
-        .verify_proof((&storage_hash).into(), &key, storage_proof)
-        .unwrap()
-        .unwrap();
-
-    if be_bytes_geq(&balance, &expected_balance.to_be_bytes()) {
-        panic!("Account balance is smaller than the expected balance.");
-    }
+    // Compute storage slot: for a Solidity balance mapping, it's Keccak(abi.encode(mapping_key, uint256(mapping_slot)))
+    let slot = mapping_slot(layout, &user_address, &balance_slot);
+    // Verify the contract's account proof (accountProof in eth_getProof) under the state root, then the
+    // balance slot (storageProof) under its storage root, an absent slot holds a zero balance
+    let (contract, balance) = verify_contract_storage(&root, &contract_address, account_proof, &slot, storage_proof);
+
+    // Read whether the holder is blacklisted (frozen) where the token records it, under the same storage
+    // root. A flag packed in the balance slot is always masked off, so that a frozen account proves its
+    // balance rather than 2^255 + balance.
+    let (blacklist, balance, blacklisted) = match blacklist {
+        Some(BlacklistProof::Mapping { blacklist_slot, storage_proof }) => {
+            let slot = mapping_slot(layout, &user_address, &blacklist_slot);
+            let value = verify_storage_value(&contract.storage_root, &Keccak256::digest(slot), storage_proof);
+            (Some(BlacklistLayout::Mapping(blacklist_slot)), balance, value.iter().any(|byte| *byte != 0))
+        }
+        Some(BlacklistProof::BalanceHighBit) => {
+            let (balance, blacklisted) = split_balance_high_bit(&balance);
+            (Some(BlacklistLayout::BalanceHighBit), balance, blacklisted)
+        }
+        None => (None, balance, false),
+    };
+
+    // Check the balance against the claim (bound, range or tiers), the resulting claim is committed
+    let balance_claim = check_balance_claim(&balance, balance_claim);
+
+    // Optionally check that the address is in the address allowlist tree, whose root alone is committed
+    let address_allowlist_root = address_allowlist.map(|address_allowlist| {
+        verify_merkle_proof(&address_leaf(&user_address), &address_allowlist);
+        address_allowlist.root
+    });
+
+    // Optionally check that the address is not in the sorted sanctions tree, whose root alone is committed
+    let sanctions_root = sanctions.map(|sanctions| verify_non_membership(&user_address, &sanctions));
+
+    // Hiding commitment to the exact balance, only computed if the prover asked for one
+    let balance_commitment = balance_blinding.map(|blinding| balance_commitment(&balance, &blinding));
+
+    // Salted commitment to the address, only computed if the prover asked for one
+    let address_commitment = address_salt.map(|salt| address_commitment(&user_address, &salt));
 
     env::commit(
         &(ContractProofOutput {
-            storage_hash,
+            root,
+            storage_hash: contract.storage_root,
+            code_hash: contract.code_hash,
             block_hash,
-            expected_balance,
+            balance_claim,
             contract_address,
             balance_slot,
+            layout,
             message,
+            address_commitment,
+            balance_commitment,
+            address_allowlist_root,
+            sanctions_root,
+            not_frozen: blacklist.is_some() && !blacklisted,
+            blacklist,
         }),
     );
 }
@@ -0,0 +1,63 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::{mapping_slot, SlotLayout},
+    proof_io::{BalanceClaim, CTokenProofInput, CTokenProofOutput, CashProof, TokenSlot},
+    proof_utils::{check_balance_claim, compound_exchange_rate, compound_underlying, slot_to_address, verify_account, verify_contract_storage, verify_signed_message, verify_storage_value},
+};
+use risc0_zkvm::guest::env;
+use sha3::{Digest, Keccak256};
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let CTokenProofInput { ctoken_address, ctoken_layout, user_address, root, block_hash, account_proof, balance_proof, total_borrows_proof, total_reserves_proof, total_supply_proof, initial_exchange_rate_proof, cash, threshold, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Verify the cToken's account proof, then every value under its storage root
+    let ctoken = verify_account(&root, &ctoken_address, account_proof);
+    let read = |slot: &[u8; 32], proof| verify_storage_value(&ctoken.storage_root, &Keccak256::digest(slot), proof);
+    let slot = mapping_slot(SlotLayout::Solidity, &user_address, &ctoken_layout.balance_slot);
+    let balance = read(&slot, balance_proof);
+    let total_borrows = read(&ctoken_layout.total_borrows_slot, total_borrows_proof);
+    let total_reserves = read(&ctoken_layout.total_reserves_slot, total_reserves_proof);
+    let total_supply = read(&ctoken_layout.total_supply_slot, total_supply_proof);
+
+    // Cash is the cToken's own ether balance for cETH, otherwise its balance of the underlying token
+    // whose address it stores
+    let (underlying_token, cash) = match cash {
+        CashProof::Native => (None, ctoken.balance.to_vec()),
+        CashProof::Token { underlying_proof, underlying_address, balance_slot, layout, account_proof, storage_proof } => {
+            if slot_to_address(&read(&ctoken_layout.underlying_slot, underlying_proof)) != underlying_address {
+                panic!("Token is not the cToken's underlying.");
+            }
+            let slot = mapping_slot(layout, &ctoken_address, &balance_slot);
+            let (token, cash) = verify_contract_storage(&root, &underlying_address, account_proof, &slot, storage_proof);
+            (Some(TokenSlot { address: underlying_address, code_hash: token.code_hash, balance_slot, layout }), cash)
+        }
+    };
+
+    // Underlying amount at the stored exchange rate, both rounded down as in balanceOfUnderlying
+    let initial_exchange_rate = read(&ctoken_layout.initial_exchange_rate_slot, initial_exchange_rate_proof);
+    let exchange_rate = compound_exchange_rate(&cash, &total_borrows, &total_reserves, &total_supply, &initial_exchange_rate);
+    let underlying = compound_underlying(&balance, &exchange_rate);
+    check_balance_claim(&underlying, BalanceClaim::AtLeast(threshold));
+
+    env::commit(
+        &(CTokenProofOutput {
+            ctoken_address,
+            code_hash: ctoken.code_hash,
+            ctoken_layout,
+            underlying: underlying_token,
+            exchange_rate,
+            root,
+            block_hash,
+            threshold,
+            message,
+        }),
+    );
+}
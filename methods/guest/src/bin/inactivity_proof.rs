@@ -0,0 +1,55 @@
+#![no_main]
+
+use primitive_types::U256;
+use proof_core::{
+    eth_utils::EMPTY_CODE_HASH,
+    proof_io::{BalanceClaim, InactivityProofInput, InactivityProofOutput},
+    proof_utils::{check_balance_claim, verify_account, verify_block_header, verify_signed_message},
+};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let InactivityProofInput {user_address, start_header, end_header, start_block_hash, end_block_hash, start_account_proof, end_account_proof, expected_balance, signature, message} = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Tie both state roots to their block hashes through the headers
+    let start_block = verify_block_header(&start_header, &start_block_hash);
+    let end_block = verify_block_header(&end_header, &end_block_hash);
+    if start_block.number > end_block.number {
+        panic!("Start block must not be after the end block.");
+    }
+
+    // Verify Merkle-Patricia trie proofs (accountProof in eth_getProof) at both blocks
+    let start_account = verify_account(&start_block.state_root, &user_address, start_account_proof);
+    let end_account = verify_account(&end_block.state_root, &user_address, end_account_proof);
+
+    // An account without code can only spend by sending a transaction, which increments its nonce:
+    // with an unchanged nonce, the balance can only have grown between the two blocks
+    if start_account.code_hash != EMPTY_CODE_HASH || end_account.code_hash != EMPTY_CODE_HASH {
+        panic!("Account must be an externally owned account.");
+    }
+    if start_account.nonce != end_account.nonce {
+        panic!("Account nonce changed between the two blocks.");
+    }
+    check_balance_claim(&start_account.balance, BalanceClaim::AtLeast(expected_balance));
+    if U256::from_big_endian(&end_account.balance) < U256::from_big_endian(&start_account.balance) {
+        panic!("Account balance decreased between the two blocks.");
+    }
+
+    env::commit(
+        &(InactivityProofOutput {
+            start_block_hash,
+            end_block_hash,
+            start_block_number: start_block.number,
+            end_block_number: end_block.number,
+            expected_balance,
+            message,
+        }),
+    );
+}
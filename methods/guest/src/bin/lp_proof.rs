@@ -0,0 +1,55 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::{mapping_slot, packed_field, to_word, SlotLayout},
+    proof_io::{BalanceClaim, LpProofInput, LpProofOutput},
+    proof_utils::{check_balance_claim, convert_to_assets, slot_to_address, verify_account, verify_signed_message, verify_storage_value},
+};
+use risc0_zkvm::guest::env;
+use sha3::{Digest, Keccak256};
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let LpProofInput { pair_address, pair_layout, user_address, root, block_hash, account_proof, balance_proof, total_supply_proof, token0_proof, token1_proof, reserves_proof, threshold0, threshold1, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Verify the pair's account proof, then every value under its storage root
+    let pair = verify_account(&root, &pair_address, account_proof);
+    let read = |slot: &[u8; 32], proof| verify_storage_value(&pair.storage_root, &Keccak256::digest(slot), proof);
+    let slot = mapping_slot(SlotLayout::Solidity, &user_address, &pair_layout.balance_slot);
+    let lp_balance = read(&slot, balance_proof);
+    let total_supply = read(&pair_layout.total_supply_slot, total_supply_proof);
+    let token0 = slot_to_address(&read(&pair_layout.token0_slot, token0_proof));
+    let token1 = slot_to_address(&read(&pair_layout.token1_slot, token1_proof));
+
+    // uint112 reserve0 and reserve1 are packed above each other, below the uint32 timestamp
+    let reserves = read(&pair_layout.reserves_slot, reserves_proof);
+    let reserve0 = to_word(packed_field(&reserves, 0, 14));
+    let reserve1 = to_word(packed_field(&reserves, 14, 14));
+    let reserves_timestamp = packed_field(&reserves, 28, 4).as_u32();
+
+    // Pro-rata amounts of each token, rounded down as on burn, must reach their thresholds
+    check_balance_claim(&convert_to_assets(&lp_balance, &reserve0, &total_supply), BalanceClaim::AtLeast(threshold0));
+    check_balance_claim(&convert_to_assets(&lp_balance, &reserve1, &total_supply), BalanceClaim::AtLeast(threshold1));
+
+    env::commit(
+        &(LpProofOutput {
+            pair_address,
+            code_hash: pair.code_hash,
+            pair_layout,
+            token0,
+            token1,
+            reserves_timestamp,
+            root,
+            block_hash,
+            threshold0,
+            threshold1,
+            message,
+        }),
+    );
+}
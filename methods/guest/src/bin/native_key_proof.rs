@@ -0,0 +1,33 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::private_key_to_address,
+    proof_io::{NativeKeyProofInput, NativeKeyProofOutput},
+    proof_utils::{check_balance_claim, verify_account},
+};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let NativeKeyProofInput {private_key, root, block_hash, account_proof, balance_claim, context} = env::read();
+
+    // Derive the account address from the private key: ownership is implied by knowledge of the key,
+    // which stays private to the guest
+    let user_address = private_key_to_address(&private_key).expect("Invalid private key.");
+
+    // Verify Merkle-Patricia trie proof (accountProof in eth_getProof), an absent account has a zero balance
+    let balance = verify_account(&root, &user_address, account_proof).balance;
+
+    // Check the balance against the claim (bound, range or tiers), the resulting claim is committed
+    let balance_claim = check_balance_claim(&balance, balance_claim);
+
+    env::commit(
+        &(NativeKeyProofOutput {
+            root,
+            block_hash,
+            balance_claim,
+            context,
+        }),
+    );
+}
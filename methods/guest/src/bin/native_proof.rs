@@ -1,46 +1,63 @@
 #![no_main]
 
-use eth_trie::Trie;
 use proof_core::{
-    eth_utils::{decode_ethereum_rlp, vec_be_bytes_geq},
+    eth_utils::{address_commitment, balance_commitment},
+    merkle::{address_leaf, verify_merkle_proof, verify_non_membership},
     proof_io::{NativeProofInput, NativeProofOutput},
-    proof_utils::{create_eth_trie, verify_signed_message},
+    proof_utils::{check_balance_claim, evaluate_predicate, verify_account, verify_signed_message},
 };
 use risc0_zkvm::guest::env;
-use sha3::{Digest, Keccak256};
 
 risc0_zkvm::guest::entry!(main);
 
 pub fn main() {
-    let NativeProofInput {user_address, root, block_hash, account_proof, expected_balance, signature, message} = env::read();
+    let NativeProofInput {user_address, root, block_hash, account_proof, balance_claim, signature, message, address_salt, balance_blinding, predicate, address_allowlist, sanctions} = env::read();
 
     // Verify signed message corresponds to provided address
     // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
     // as soon as those are made available
     verify_signed_message(&signature, &message, &user_address);
 
-    // Verify Merkle-Patricia trie proof (accountProof in eth_getProof)
-    let trie = create_eth_trie();
-    let key = Keccak256::digest(&user_address).to_vec();
-    let result = trie
-        .verify_proof((&root).into(), &key, account_proof)
-        .unwrap()
-        .unwrap();
+    // Verify Merkle-Patricia trie proof (accountProof in eth_getProof), an absent account is empty
+    let account = verify_account(&root, &user_address, account_proof);
+    let balance = account.balance;
 
-    let mut result = decode_ethereum_rlp(result.as_slice()).unwrap();
-
-    // balance is second element in the returned array
-    let balance = result.swap_remove(1);
-    if vec_be_bytes_geq(&balance, &expected_balance.to_be_bytes().to_vec()) {
-        panic!("Account balance is smaller than the expected balance.");
+    // Check the optional predicate over the account fields, it is committed as is
+    if let Some(predicate) = &predicate {
+        if !evaluate_predicate(&account, predicate) {
+            panic!("Account does not satisfy the predicate.");
+        }
     }
 
+    // Check the balance against the claim (bound, range or tiers), the resulting claim is committed
+    let balance_claim = check_balance_claim(&balance, balance_claim);
+
+    // Optionally check that the address is in the address allowlist tree, whose root alone is committed
+    let address_allowlist_root = address_allowlist.map(|address_allowlist| {
+        verify_merkle_proof(&address_leaf(&user_address), &address_allowlist);
+        address_allowlist.root
+    });
+
+    // Optionally check that the address is not in the sorted sanctions tree, whose root alone is committed
+    let sanctions_root = sanctions.map(|sanctions| verify_non_membership(&user_address, &sanctions));
+
+    // Hiding commitment to the exact balance, only computed if the prover asked for one
+    let balance_commitment = balance_blinding.map(|blinding| balance_commitment(&balance, &blinding));
+
+    // Salted commitment to the address, only computed if the prover asked for one
+    let address_commitment = address_salt.map(|salt| address_commitment(&user_address, &salt));
+
     env::commit(
         &(NativeProofOutput {
             root,
             block_hash,
-            expected_balance,
+            balance_claim,
             message,
+            address_commitment,
+            balance_commitment,
+            predicate,
+            address_allowlist_root,
+            sanctions_root,
         }),
     );
 }
@@ -0,0 +1,36 @@
+#![no_main]
+
+use proof_core::{
+    proof_io::{OwnerProofInput, OwnerProofOutput},
+    proof_utils::{slot_to_address, verify_contract_storage, verify_signed_message},
+};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let OwnerProofInput { contract_address, owner_slot, user_address, root, block_hash, account_proof, storage_proof, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Verify the contract's account proof, then the owner slot under its storage root
+    let (_, value) = verify_contract_storage(&root, &contract_address, account_proof, &owner_slot, storage_proof);
+
+    // The slot must hold the signer's address
+    if slot_to_address(&value) != user_address {
+        panic!("Signer is not the owner of the contract.");
+    }
+
+    env::commit(
+        &(OwnerProofOutput {
+            contract_address,
+            owner_slot,
+            root,
+            block_hash,
+            message,
+        }),
+    );
+}
@@ -0,0 +1,60 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::{mapping_slot, SlotLayout},
+    proof_io::{BalanceClaim, StethProofInput, StethProofOutput, TokenSlot},
+    proof_utils::{check_balance_claim, convert_to_assets, lido_total_pooled_ether, sum_storage_values, verify_account, verify_contract_storage, verify_signed_message, verify_storage_value},
+};
+use risc0_zkvm::guest::env;
+use sha3::{Digest, Keccak256};
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let StethProofInput { steth_address, lido_layout, user_address, root, block_hash, account_proof, shares_proof, total_shares_proof, buffered_ether_proof, cl_balance_proof, deposited_validators_proof, cl_validators_proof, wrapped, threshold, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Verify the stETH account proof, then every value under its storage root
+    let steth = verify_account(&root, &steth_address, account_proof);
+    let read = |position: &[u8; 32], proof| verify_storage_value(&steth.storage_root, &Keccak256::digest(position), proof);
+    let slot = mapping_slot(SlotLayout::Solidity, &user_address, &lido_layout.shares_slot);
+    let shares = read(&slot, shares_proof);
+    let total_shares = read(&lido_layout.total_shares_position, total_shares_proof);
+    let total_pooled_ether = lido_total_pooled_ether(
+        &read(&lido_layout.buffered_ether_position, buffered_ether_proof),
+        &read(&lido_layout.cl_balance_position, cl_balance_proof),
+        &read(&lido_layout.deposited_validators_position, deposited_validators_proof),
+        &read(&lido_layout.cl_validators_position, cl_validators_proof),
+    );
+
+    // A wstETH balance is an amount of stETH shares held by the wrapper on the holder's behalf
+    let (wrapped, shares) = match wrapped {
+        Some(wrapped) => {
+            let slot = mapping_slot(wrapped.layout, &user_address, &wrapped.balance_slot);
+            let (wsteth, wrapped_shares) = verify_contract_storage(&root, &wrapped.wsteth_address, wrapped.account_proof, &slot, wrapped.storage_proof);
+            (Some(TokenSlot { address: wrapped.wsteth_address, code_hash: wsteth.code_hash, balance_slot: wrapped.balance_slot, layout: wrapped.layout }), sum_storage_values(&[shares, wrapped_shares]))
+        }
+        None => (None, sum_storage_values(&[shares])),
+    };
+
+    // Balance in stETH, rounded down as in getPooledEthByShares, must reach the threshold
+    let balance = convert_to_assets(&shares, &total_pooled_ether, &total_shares);
+    check_balance_claim(&balance, BalanceClaim::AtLeast(threshold));
+
+    env::commit(
+        &(StethProofOutput {
+            steth_address,
+            code_hash: steth.code_hash,
+            lido_layout,
+            wrapped,
+            root,
+            block_hash,
+            threshold,
+            message,
+        }),
+    );
+}
@@ -0,0 +1,43 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::mapping_slot,
+    proof_io::{SupplyShareProofInput, SupplyShareProofOutput},
+    proof_utils::{verify_account, verify_signed_message, verify_storage_value, verify_supply_share},
+};
+use risc0_zkvm::guest::env;
+use sha3::{Digest, Keccak256};
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let SupplyShareProofInput { contract_address, balance_slot, layout, total_supply_slot, user_address, root, block_hash, account_proof, balance_proof, total_supply_proof, bps, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Verify the token's account proof, then both slots under the same storage root
+    let token = verify_account(&root, &contract_address, account_proof);
+    let slot = mapping_slot(layout, &user_address, &balance_slot);
+    let balance = verify_storage_value(&token.storage_root, &Keccak256::digest(slot), balance_proof);
+    let total_supply = verify_storage_value(&token.storage_root, &Keccak256::digest(total_supply_slot), total_supply_proof);
+
+    // balance * 10000 >= bps * totalSupply
+    verify_supply_share(&balance, &total_supply, bps);
+
+    env::commit(
+        &(SupplyShareProofOutput {
+            contract_address,
+            code_hash: token.code_hash,
+            balance_slot,
+            layout,
+            total_supply_slot,
+            root,
+            block_hash,
+            bps,
+            message,
+        }),
+    );
+}
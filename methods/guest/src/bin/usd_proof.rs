@@ -0,0 +1,47 @@
+#![no_main]
+
+use proof_core::{
+    proof_io::{UsdProofInput, UsdProofOutput},
+    proof_utils::{check_round_age, verify_asset_balance, verify_block_header, verify_chainlink_round, verify_signed_message, verify_usd_value},
+};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let UsdProofInput { user_address, header, block_hash, asset, asset_decimals, aggregator_address, aggregator_layout, feed_decimals, aggregator_proof, hot_vars_proof, transmission_proof, max_age, usd_threshold, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Tie the state root and the block's timestamp to the block hash through the header
+    let block = verify_block_header(&header, &block_hash);
+    let root = block.state_root;
+
+    // Verify the valued balance, native or token, under the state root
+    let (asset, balance) = verify_asset_balance(&root, &user_address, asset);
+
+    // Verify the aggregator's latest round under the same state root, check that it is recent enough,
+    // then value the balance with it
+    let round = verify_chainlink_round(&root, &aggregator_address, &aggregator_layout, aggregator_proof, hot_vars_proof, transmission_proof);
+    check_round_age(&round, block.timestamp, max_age);
+    verify_usd_value(&balance, round.answer, asset_decimals, feed_decimals, usd_threshold);
+
+    env::commit(
+        &(UsdProofOutput {
+            root,
+            block_hash,
+            asset,
+            asset_decimals,
+            aggregator_address,
+            feed_decimals,
+            round_id: round.round_id,
+            updated_at: round.updated_at,
+            max_age,
+            usd_threshold,
+            message,
+        }),
+    );
+}
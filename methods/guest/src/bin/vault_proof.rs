@@ -0,0 +1,63 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::mapping_slot,
+    proof_io::{BalanceClaim, TokenSlot, VaultAssets, VaultAssetsProof, VaultProofInput, VaultProofOutput},
+    proof_utils::{check_balance_claim, convert_vault_shares, sum_storage_values, verify_account, verify_contract_storage, verify_signed_message, verify_storage_value},
+};
+use risc0_zkvm::guest::env;
+use sha3::{Digest, Keccak256};
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let VaultProofInput { vault_address, balance_slot, layout, total_supply_slot, user_address, root, block_hash, account_proof, balance_proof, total_supply_proof, assets, decimals_offset, threshold, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    // Verify the vault's account proof, then the share balance and total supply under its storage root
+    let vault = verify_account(&root, &vault_address, account_proof);
+    let slot = mapping_slot(layout, &user_address, &balance_slot);
+    let shares = verify_storage_value(&vault.storage_root, &Keccak256::digest(slot), balance_proof);
+    let total_supply = verify_storage_value(&vault.storage_root, &Keccak256::digest(total_supply_slot), total_supply_proof);
+
+    // Verify the total assets under the same state root, either from the vault's own storage or from
+    // the underlying token's balance of the vault
+    let (assets, total_assets) = match assets {
+        VaultAssetsProof::Stored { slots, storage_proofs } => {
+            if slots.len() != storage_proofs.len() {
+                panic!("Expected one storage proof per total assets slot.");
+            }
+            let values: Vec<Vec<u8>> = slots.iter().zip(storage_proofs).map(|(slot, proof)| verify_storage_value(&vault.storage_root, &Keccak256::digest(slot), proof)).collect();
+            (VaultAssets::Stored(slots), sum_storage_values(&values))
+        }
+        VaultAssetsProof::Held { underlying_address, balance_slot, layout, account_proof, storage_proof } => {
+            let slot = mapping_slot(layout, &vault_address, &balance_slot);
+            let (underlying, balance) = verify_contract_storage(&root, &underlying_address, account_proof, &slot, storage_proof);
+            (VaultAssets::Held(TokenSlot { address: underlying_address, code_hash: underlying.code_hash, balance_slot, layout }), sum_storage_values(&[balance]))
+        }
+    };
+
+    // Underlying amount, rounded down and counting the vault's virtual shares, must reach the threshold
+    let underlying = convert_vault_shares(&shares, &total_assets, &total_supply, decimals_offset);
+    check_balance_claim(&underlying, BalanceClaim::AtLeast(threshold));
+
+    env::commit(
+        &(VaultProofOutput {
+            vault_address,
+            code_hash: vault.code_hash,
+            balance_slot,
+            layout,
+            total_supply_slot,
+            assets,
+            decimals_offset,
+            root,
+            block_hash,
+            threshold,
+            message,
+        }),
+    );
+}
@@ -0,0 +1,60 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::{address_vote_nullifier, format_eth_message, private_key_to_address, to_word, vote_message, vote_nullifier},
+    proof_io::{BalanceClaim, NullifierKind, ProvenBalance, VoteProofInput, VoteProofOutput, VoteWitness},
+    proof_utils::{check_balance_claim, verify_asset_balance, verify_block_header, verify_signed_message},
+};
+use primitive_types::U256;
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let VoteProofInput { witness, header, block_hash, asset, poll_id, choice, tiers } = env::read();
+
+    let (user_address, nullifier, nullifier_kind) = match witness {
+        // Derive the voter's address from the private key: ownership is implied by knowledge of the
+        // key, which also derives the nullifier so that nobody else can link it to the address
+        VoteWitness::PrivateKey(private_key) => {
+            let user_address = private_key_to_address(&private_key).expect("Invalid private key.");
+            (user_address, vote_nullifier(&private_key, &poll_id), NullifierKind::PrivateKey)
+        }
+        // The signed message must be the vote message of this poll and choice. Signatures are not
+        // unique, so the nullifier is derived from the address instead.
+        VoteWitness::Signature { user_address, signature, message } => {
+            if message != format_eth_message(vote_message(&poll_id, choice)).into_bytes() {
+                panic!("Signed message is not a vote for this poll and choice.");
+            }
+            // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+            // as soon as those are made available
+            verify_signed_message(&signature, &message, &user_address);
+            (user_address, address_vote_nullifier(&user_address, &poll_id), NullifierKind::Address)
+        }
+    };
+
+    // Tie the state root to the snapshot's block hash through the header
+    let root = verify_block_header(&header, &block_hash).state_root;
+
+    // Verify the balance at the snapshot, native or token
+    let (asset, balance) = verify_asset_balance(&root, &user_address, asset);
+
+    // Weight by tier when the poll publishes tiers, which reveals less about the voter's balance
+    let weight = match tiers {
+        Some(tiers) => check_balance_claim(&balance, BalanceClaim::Tiers(tiers)),
+        None => ProvenBalance::Equal(to_word(U256::from_big_endian(&balance))),
+    };
+
+    env::commit(
+        &(VoteProofOutput {
+            root,
+            block_hash,
+            asset,
+            poll_id,
+            choice,
+            weight,
+            nullifier,
+            nullifier_kind,
+        }),
+    );
+}
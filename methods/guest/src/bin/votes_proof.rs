@@ -0,0 +1,55 @@
+#![no_main]
+
+use proof_core::{
+    eth_utils::{array_element_slot, mapping_slot, packed_field, to_word, SlotLayout},
+    proof_io::{BalanceClaim, VotesProofInput, VotesProofOutput},
+    proof_utils::{check_balance_claim, verify_account, verify_signed_message, verify_storage_value},
+};
+use risc0_zkvm::guest::env;
+use sha3::{Digest, Keccak256};
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let VotesProofInput { token_address, checkpoints_slot, key_bytes, user_address, root, block_hash, account_proof, length_proof, checkpoint_proof, threshold, signature, message } = env::read();
+
+    // Verify signed message corresponds to provided address
+    // NOTE: Naive ECDSA verification is extremely costly, should be replaced by accelerated circuit
+    // as soon as those are made available
+    verify_signed_message(&signature, &message, &user_address);
+
+    if key_bytes == 0 || key_bytes > 8 {
+        panic!("Checkpoint key must be between 1 and 8 bytes.");
+    }
+
+    // Verify the token's account proof, then the length of the account's checkpoints array
+    let token = verify_account(&root, &token_address, account_proof);
+    let array_slot = mapping_slot(SlotLayout::Solidity, &user_address, &checkpoints_slot);
+    let length = packed_field(&verify_storage_value(&token.storage_root, &Keccak256::digest(array_slot), length_proof), 0, 32).as_u64();
+
+    // The last checkpoint holds the current votes above its key, an account without checkpoints has none
+    let (checkpoint_key, votes) = match length {
+        0 => (None, [0_u8; 32]),
+        length => {
+            let slot = array_element_slot(&array_slot, length - 1);
+            let checkpoint = verify_storage_value(&token.storage_root, &Keccak256::digest(slot), checkpoint_proof);
+            let key = packed_field(&checkpoint, 0, key_bytes as usize).as_u64();
+            (Some(key), to_word(packed_field(&checkpoint, key_bytes as usize, 32 - key_bytes as usize)))
+        }
+    };
+    check_balance_claim(&votes, BalanceClaim::AtLeast(threshold));
+
+    env::commit(
+        &(VotesProofOutput {
+            token_address,
+            code_hash: token.code_hash,
+            checkpoints_slot,
+            key_bytes,
+            checkpoint_key,
+            root,
+            block_hash,
+            threshold,
+            message,
+        }),
+    );
+}
@@ -1,8 +1,13 @@
 use k256::{
-    ecdsa::{recoverable::Signature, signature::Signature as _, VerifyingKey},
+    ecdsa::{
+        recoverable::Signature,
+        signature::{Signature as _, Signer},
+        SigningKey, VerifyingKey,
+    },
     elliptic_curve::sec1::ToEncodedPoint,
 };
-use prefix_hex::decode;
+use prefix_hex::{decode, encode};
+use primitive_types::U256;
 use rlp::{DecoderError, Rlp};
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
@@ -21,6 +26,88 @@ pub struct EthGetBlockBody {
     pub number: String,
     pub block_hash: [u8; 32],
     pub storage_hash: [u8; 32],
+    // RLP-encoded header, whose Keccak hash is the block hash
+    pub header_rlp: Vec<u8>,
+}
+
+// keccak256 of empty code: the code hash of every externally owned account
+pub const EMPTY_CODE_HASH: [u8; 32] =
+    hex_literal::hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+// root of an empty trie: the storage root of every account without storage
+pub const EMPTY_STORAGE_ROOT: [u8; 32] =
+    hex_literal::hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+
+// Ethereum account, as stored RLP-encoded in the state trie
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Account {
+    pub nonce: u64,
+    // 32-byte big-endian word in wei
+    pub balance: [u8; 32],
+    pub storage_root: [u8; 32],
+    pub code_hash: [u8; 32],
+}
+
+impl Account {
+    // State of an account that does not exist in the trie
+    pub fn empty() -> Self {
+        Account {
+            nonce: 0,
+            balance: [0_u8; 32],
+            storage_root: EMPTY_STORAGE_ROOT,
+            code_hash: EMPTY_CODE_HASH,
+        }
+    }
+}
+
+pub fn decode_account(encoded: &[u8]) -> Result<Account, DecoderError> {
+    let rlp = Rlp::new(encoded);
+    if rlp.item_count()? != 4 {
+        return Err(DecoderError::RlpIncorrectListLen);
+    }
+
+    Ok(Account {
+        nonce: rlp.val_at(0)?,
+        balance: pad_word(&rlp.val_at::<Vec<u8>>(1)?)?,
+        storage_root: pad_word(&rlp.val_at::<Vec<u8>>(2)?)?,
+        code_hash: pad_word(&rlp.val_at::<Vec<u8>>(3)?)?,
+    })
+}
+
+// Fields of a block header needed to anchor state proofs
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BlockHeader {
+    pub state_root: [u8; 32],
+    pub number: u64,
+    // unix time (seconds) at which the block was produced
+    pub timestamp: u64,
+}
+
+// Decodes an RLP-encoded block header, whose hash is the block hash
+pub fn decode_block_header(encoded: &[u8]) -> Result<BlockHeader, DecoderError> {
+    let rlp = Rlp::new(encoded);
+    // pre-London headers have 15 fields, later forks append more
+    if rlp.item_count()? < 15 {
+        return Err(DecoderError::RlpIncorrectListLen);
+    }
+    let state_root: Vec<u8> = rlp.val_at(3)?;
+
+    Ok(BlockHeader {
+        state_root: state_root
+            .try_into()
+            .map_err(|_| DecoderError::RlpInvalidLength)?,
+        number: rlp.val_at(8)?,
+        timestamp: rlp.val_at(11)?,
+    })
+}
+
+// Left-pads a big-endian value of at most 32 bytes to a full word
+fn pad_word(value: &[u8]) -> Result<[u8; 32], DecoderError> {
+    if value.len() > 32 {
+        return Err(DecoderError::RlpIsTooBig);
+    }
+    let mut word = [0_u8; 32];
+    word[32 - value.len()..].copy_from_slice(value);
+    Ok(word)
 }
 
 pub fn decode_ethereum_rlp(encoded: &[u8]) -> Result<Vec<Vec<u8>>, DecoderError> {
@@ -52,6 +139,18 @@ pub fn derive_address(vk: &VerifyingKey) -> Result<[u8; 20], Box<dyn std::error:
     Ok(Keccak256::digest(encoded)[12..].try_into()?)
 }
 
+pub fn private_key_to_address(private_key: &[u8]) -> Result<[u8; 20], Box<dyn std::error::Error>> {
+    let signing_key = SigningKey::from_bytes(private_key).map_err(|_| "Invalid private key")?;
+    derive_address(&signing_key.verifying_key())
+}
+
+pub fn sign_message(private_key: &[u8], msg: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let signing_key = SigningKey::from_bytes(private_key).map_err(|_| "Invalid private key")?;
+    // Recoverable signature over Keccak256(msg), encoded as r || s || v with v = {00, 01}
+    let signature: Signature = signing_key.try_sign(msg).map_err(|_| "Signing failed")?;
+    Ok(signature.as_ref().to_vec())
+}
+
 pub fn check_signature(
     sig: &str,
     msg: &str,
@@ -62,6 +161,123 @@ pub fn check_signature(
     Ok(pubkey == decode::<[u8; 20]>(addr).unwrap())
 }
 
+pub fn address_commitment(address: &[u8; 20], salt: &[u8; 32]) -> [u8; 32] {
+    Keccak256::digest([address.as_slice(), salt.as_slice()].concat()).into()
+}
+
+// Identifier of an off-chain poll, keccak of its name
+pub fn poll_id(poll: &str) -> [u8; 32] {
+    Keccak256::digest(poll.as_bytes()).into()
+}
+
+// Message signed by a voter who cannot export their key, which binds their choice to the poll
+pub fn vote_message(poll_id: &[u8; 32], choice: u32) -> String {
+    format!(
+        "Vote for choice {} in poll {}",
+        choice,
+        encode(poll_id.to_vec())
+    )
+}
+
+// Domain separators of vote nullifiers, so that they cannot collide with other hashes of a key or
+// address, nor with each other
+const VOTE_NULLIFIER_TAG: &[u8] = b"vote-nullifier";
+const ADDRESS_VOTE_NULLIFIER_TAG: &[u8] = b"vote-nullifier-address";
+
+// Per-poll nullifier of a voter, keccak(tag || private key || poll ID). It is the same for every
+// vote of a key in a poll, and only the key's holder can compute it, unlike a hash of the address.
+pub fn vote_nullifier(private_key: &[u8; 32], poll_id: &[u8; 32]) -> [u8; 32] {
+    Keccak256::digest(
+        [
+            VOTE_NULLIFIER_TAG,
+            private_key.as_slice(),
+            poll_id.as_slice(),
+        ]
+        .concat(),
+    )
+    .into()
+}
+
+// Per-poll nullifier of a signed vote, keccak(tag || address || poll ID). ECDSA signatures are not
+// unique, so they cannot be nullifiers: anyone who guesses the address can recompute this one.
+pub fn address_vote_nullifier(address: &[u8; 20], poll_id: &[u8; 32]) -> [u8; 32] {
+    Keccak256::digest(
+        [
+            ADDRESS_VOTE_NULLIFIER_TAG,
+            address.as_slice(),
+            poll_id.as_slice(),
+        ]
+        .concat(),
+    )
+    .into()
+}
+
+// Storage layout of a compiler, which determines where mapping entries are stored
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlotLayout {
+    // keccak(abi.encode(key, uint256(slot)))
+    Solidity,
+    // keccak(concat(slot, key))
+    Vyper,
+}
+
+// Storage slot of an address-keyed mapping entry, e.g. an ERC20 balance
+pub fn mapping_slot(layout: SlotLayout, key: &[u8; 20], slot: &[u8; 32]) -> [u8; 32] {
+    let mut padded_key = [0_u8; 32];
+    padded_key[12..].copy_from_slice(key);
+    word_mapping_slot(layout, &padded_key, slot)
+}
+
+// Storage slot of a mapping entry whose key is a value type, given as a 32-byte word
+pub fn word_mapping_slot(layout: SlotLayout, key: &[u8; 32], slot: &[u8; 32]) -> [u8; 32] {
+    let prehash = match layout {
+        SlotLayout::Solidity => [key.as_slice(), slot.as_slice()].concat(),
+        SlotLayout::Vyper => [slot.as_slice(), key.as_slice()].concat(),
+    };
+    Keccak256::digest(prehash).into()
+}
+
+// Slot of a struct member or array element stored `offset` slots after `slot`, wrapping around as
+// storage does
+pub fn slot_offset(slot: &[u8; 32], offset: u64) -> [u8; 32] {
+    let (slot, _) = U256::from_big_endian(slot).overflowing_add(U256::from(offset));
+    let mut slot_bytes = [0_u8; 32];
+    slot.to_big_endian(&mut slot_bytes);
+    slot_bytes
+}
+
+// Slot of the element at `index` of a dynamic array whose length is stored at `slot`, the elements
+// being laid out one slot each from keccak(slot)
+pub fn array_element_slot(slot: &[u8; 32], index: u64) -> [u8; 32] {
+    slot_offset(&Keccak256::digest(slot).into(), index)
+}
+
+// Reads a field packed in a storage word, `size` bytes long and `offset` bytes away from the
+// word's least significant end, as Solidity packs consecutive small variables
+pub fn packed_field(word: &[u8], offset: usize, size: usize) -> U256 {
+    let value = U256::from_big_endian(word) >> (offset * 8);
+    if size >= 32 {
+        value
+    } else {
+        value & ((U256::one() << (size * 8)) - 1)
+    }
+}
+
+// Encodes a value as a 32-byte big-endian word, as storage holds it
+pub fn to_word(value: U256) -> [u8; 32] {
+    let mut word = [0_u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+pub fn balance_commitment(balance: &[u8], blinding: &[u8; 32]) -> [u8; 32] {
+    // Balances are committed as 32-byte big-endian words, whatever their encoded length
+    let mut padded_balance = [0_u8; 32];
+    padded_balance[32 - balance.len()..].copy_from_slice(balance);
+    Keccak256::digest([padded_balance.as_slice(), blinding.as_slice()].concat()).into()
+}
+
 pub fn vec_be_bytes_geq(a: &Vec<u8>, b: &Vec<u8>) -> bool {
     if a.len() > b.len() {
         true
@@ -101,6 +317,60 @@ mod tests {
     use super::*;
     use hex_literal::hex;
 
+    #[test]
+    fn test_decode_account() {
+        let mut stream = rlp::RlpStream::new_list(4);
+        stream.append(&100_u64);
+        stream.append(&vec![0x0d_u8, 0xe0, 0xb6, 0xb3, 0xa7, 0x64, 0x00, 0x00]);
+        stream.append(&EMPTY_STORAGE_ROOT.to_vec());
+        stream.append(&EMPTY_CODE_HASH.to_vec());
+
+        let account = decode_account(&stream.out()).unwrap();
+        let mut balance = [0_u8; 32];
+        balance[24..].copy_from_slice(&hex!("0de0b6b3a7640000"));
+
+        assert_eq!(account.nonce, 100);
+        assert_eq!(account.balance, balance);
+        assert_eq!(account.storage_root, EMPTY_STORAGE_ROOT);
+        assert_eq!(account.code_hash, EMPTY_CODE_HASH);
+        assert!(decode_account(&hex!("c3010203")).is_err());
+    }
+
+    #[test]
+    fn test_decode_block_header() {
+        let mut stream = rlp::RlpStream::new_list(15);
+        for _ in 0..3 {
+            stream.append(&[0_u8; 32].to_vec());
+        }
+        stream.append(&EMPTY_STORAGE_ROOT.to_vec());
+        for _ in 4..8 {
+            stream.append(&[0_u8; 32].to_vec());
+        }
+        stream.append(&17_000_000_u64);
+        for _ in 9..11 {
+            stream.append(&0_u64);
+        }
+        stream.append(&1_681_000_000_u64);
+        for _ in 12..15 {
+            stream.append(&0_u64);
+        }
+
+        assert_eq!(
+            decode_block_header(&stream.out()).unwrap(),
+            BlockHeader {
+                state_root: EMPTY_STORAGE_ROOT,
+                number: 17_000_000,
+                timestamp: 1_681_000_000,
+            }
+        );
+        assert!(decode_block_header(&hex!("c3010203")).is_err());
+    }
+
+    #[test]
+    fn test_empty_code_hash() {
+        assert_eq!(Keccak256::digest([]).as_slice(), EMPTY_CODE_HASH);
+    }
+
     #[test]
     fn test_decode_ethereum_rlp() {
         let encoded = hex!(
@@ -166,6 +436,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_private_key_to_address() {
+        let private_key = hex!("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let expected_address: [u8; 20] = hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+
+        assert_eq!(
+            private_key_to_address(&private_key).unwrap(),
+            expected_address,
+            "Derived address is incorrect"
+        );
+    }
+
+    #[test]
+    fn test_sign_message() {
+        let private_key = hex!("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let msg = format_eth_message("hello world".to_string());
+        let sig = sign_message(&private_key, msg.as_bytes()).unwrap();
+
+        assert_eq!(sig.len(), 65, "Signature length is incorrect");
+        assert!(sig[64] <= 1, "Recovery id should be 00 or 01");
+        assert!(check_signature(
+            &prefix_hex::encode(sig),
+            &msg,
+            "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+        )
+        .unwrap());
+    }
+
     #[test]
     fn test_check_signature() {
         let user_address = "0x63d90be9ac2859c0b94421281747cefe89b4223c";
@@ -180,6 +478,142 @@ mod tests {
         assert!(check_signature(&sig, msg, user_address).unwrap());
     }
 
+    #[test]
+    fn test_address_commitment() {
+        let address: [u8; 20] = hex!("63d90be9ac2859c0b94421281747cefe89b4223c");
+        let salt = [7_u8; 32];
+        let mut prehash = address.to_vec();
+        prehash.extend_from_slice(&salt);
+
+        assert_eq!(
+            address_commitment(&address, &salt).as_slice(),
+            Keccak256::digest(&prehash).as_slice(),
+            "Address commitment is incorrect"
+        );
+        assert_ne!(
+            address_commitment(&address, &salt),
+            address_commitment(&address, &[8_u8; 32]),
+            "Address commitment should depend on the salt"
+        );
+    }
+
+    #[test]
+    fn test_mapping_slot() {
+        let key: [u8; 20] = hex!("63d90be9ac2859c0b94421281747cefe89b4223c");
+        let mut slot = [0_u8; 32];
+        slot[31] = 9;
+        let padded_key = hex!("00000000000000000000000063d90be9ac2859c0b94421281747cefe89b4223c");
+
+        assert_eq!(
+            mapping_slot(SlotLayout::Solidity, &key, &slot).as_slice(),
+            Keccak256::digest([padded_key, slot].concat()).as_slice()
+        );
+        assert_eq!(
+            mapping_slot(SlotLayout::Vyper, &key, &slot).as_slice(),
+            Keccak256::digest([slot, padded_key].concat()).as_slice()
+        );
+    }
+
+    #[test]
+    fn test_packed_field() {
+        // uint32 round ID packed above a bytes16, a uint40 and a uint8
+        let word = hex!("0000000000000000002a0a000000000500000000000000000000000000000000");
+
+        assert_eq!(packed_field(&word, 22, 4), U256::from(42));
+        assert_eq!(packed_field(&word, 21, 1), U256::from(10));
+        assert_eq!(packed_field(&word, 16, 5), U256::from(5));
+        assert_eq!(packed_field(&[1, 0], 0, 32), U256::from(256));
+        assert_eq!(to_word(packed_field(&word, 16, 5))[27..], [0, 0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn test_slot_offset() {
+        let mut expected = [0_u8; 32];
+        expected[31] = 4;
+
+        assert_eq!(slot_offset(&[0_u8; 32], 4), expected);
+        assert_eq!(slot_offset(&[0xff_u8; 32], 5), expected);
+    }
+
+    #[test]
+    fn test_array_element_slot() {
+        let slot = mapping_slot(SlotLayout::Solidity, &[1_u8; 20], &[0_u8; 32]);
+        let first: [u8; 32] = Keccak256::digest(slot).into();
+
+        assert_eq!(array_element_slot(&slot, 0), first);
+        assert_eq!(array_element_slot(&slot, 2), slot_offset(&first, 2));
+    }
+
+    #[test]
+    fn test_vote_nullifier() {
+        let poll_id = poll_id("treasury-2024");
+        let private_key = hex!("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let address = private_key_to_address(&private_key).unwrap();
+        let nullifier = vote_nullifier(&private_key, &poll_id);
+
+        assert_eq!(nullifier, vote_nullifier(&private_key, &poll_id));
+        assert_ne!(
+            nullifier,
+            vote_nullifier(&private_key, &[0_u8; 32]),
+            "Nullifier should depend on the poll"
+        );
+        assert_ne!(
+            nullifier,
+            vote_nullifier(&[1_u8; 32], &poll_id),
+            "Nullifier should depend on the key"
+        );
+        // the public address and poll ID alone do not give the nullifier
+        let mut address_word = [0_u8; 32];
+        address_word[12..].copy_from_slice(&address);
+        assert_ne!(
+            nullifier,
+            <[u8; 32]>::from(Keccak256::digest(
+                [address.as_slice(), poll_id.as_slice()].concat()
+            ))
+        );
+        assert_ne!(nullifier, vote_nullifier(&address_word, &poll_id));
+    }
+
+    #[test]
+    fn test_address_vote_nullifier() {
+        let poll_id = poll_id("treasury-2024");
+
+        assert_eq!(
+            vote_message(&[0xab_u8; 32], 2),
+            format!("Vote for choice 2 in poll 0x{}", "ab".repeat(32))
+        );
+        assert_ne!(
+            address_vote_nullifier(&[1_u8; 20], &poll_id),
+            address_vote_nullifier(&[1_u8; 20], &[0_u8; 32]),
+            "Nullifier should depend on the poll"
+        );
+        // a key's two nullifiers differ, the tally rejects polls that mix them
+        let mut address_word = [0_u8; 32];
+        address_word[12..].copy_from_slice(&[1_u8; 20]);
+        assert_ne!(
+            address_vote_nullifier(&[1_u8; 20], &poll_id),
+            vote_nullifier(&address_word, &poll_id)
+        );
+    }
+
+    #[test]
+    fn test_balance_commitment() {
+        let blinding = [7_u8; 32];
+        let mut padded_balance = [0_u8; 32];
+        padded_balance[30..].copy_from_slice(&[1, 0]);
+
+        assert_eq!(
+            balance_commitment(&[1, 0], &blinding),
+            balance_commitment(&padded_balance, &blinding),
+            "Balance commitment should not depend on the encoded length"
+        );
+        assert_ne!(
+            balance_commitment(&[1, 0], &blinding),
+            balance_commitment(&[1, 1], &blinding),
+            "Balance commitment should depend on the balance"
+        );
+    }
+
     #[test]
     fn test_vec_be_bytes_geq() {
         assert!(
@@ -1,3 +1,4 @@
 pub mod eth_utils;
+pub mod merkle;
 pub mod proof_io;
 pub mod proof_utils;
@@ -0,0 +1,178 @@
+use crate::proof_io::{MerkleProof, NonMembershipProof};
+use sha3::{Digest, Keccak256};
+
+// Leaf of an address tree. Leaves (20-byte preimages) cannot be mistaken for inner nodes (64 bytes).
+pub fn address_leaf(address: &[u8; 20]) -> [u8; 32] {
+    Keccak256::digest(address).into()
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Keccak256::digest([left.as_slice(), right.as_slice()].concat()).into()
+}
+
+// Root of the tree holding `leaf` at `index`, given the sibling of each level from the leaves up
+pub fn merkle_root(leaf: &[u8; 32], index: u64, path: &[[u8; 32]]) -> [u8; 32] {
+    if path.len() < 64 && index >> path.len() != 0 {
+        panic!("Merkle index is outside of the tree.");
+    }
+
+    path.iter()
+        .enumerate()
+        .fold(*leaf, |node, (level, sibling)| match (index >> level) & 1 {
+            0 => hash_pair(&node, sibling),
+            _ => hash_pair(sibling, &node),
+        })
+}
+
+// Verifies that `leaf` is at the proof's index of the tree with the proof's root
+pub fn verify_merkle_proof(leaf: &[u8; 32], proof: &MerkleProof) {
+    if merkle_root(leaf, proof.index, &proof.path) != proof.root {
+        panic!("Invalid Merkle proof.");
+    }
+}
+
+// Verifies that an address is not a leaf of a tree of sorted address leaves, by proving the two
+// adjacent leaves around it, and returns the tree's root. The tree must have been built sorted,
+// which the root's publisher vouches for.
+pub fn verify_non_membership(address: &[u8; 20], proof: &NonMembershipProof) -> [u8; 32] {
+    verify_merkle_proof(&address_leaf(&proof.low), &proof.low_proof);
+    verify_merkle_proof(&address_leaf(&proof.high), &proof.high_proof);
+    if proof.low_proof.root != proof.high_proof.root {
+        panic!("Merkle proofs are not for the same tree.");
+    }
+    if proof.low_proof.index.checked_add(1) != Some(proof.high_proof.index) {
+        panic!("Bracketing leaves are not adjacent.");
+    }
+    if !(proof.low < *address && *address < proof.high) {
+        panic!("Address is not between the bracketing leaves.");
+    }
+
+    proof.low_proof.root
+}
+
+// Levels of the tree over these leaves, from the leaves up to the root. Levels of odd length are
+// padded with a zero node.
+pub fn merkle_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while levels[levels.len() - 1].len() > 1 {
+        let level = &levels[levels.len() - 1];
+        let parents = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&[0_u8; 32])))
+            .collect();
+        levels.push(parents);
+    }
+
+    levels
+}
+
+// Sibling path of the leaf at `index`, as expected by `merkle_root`
+pub fn merkle_path(levels: &[Vec<[u8; 32]>], index: usize) -> Vec<[u8; 32]> {
+    levels[..levels.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(level, nodes)| {
+            let sibling = (index >> level) ^ 1;
+            nodes.get(sibling).copied().unwrap_or([0_u8; 32])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_path() {
+        let leaves: Vec<[u8; 32]> = (0..5_u8).map(|i| address_leaf(&[i; 20])).collect();
+        let levels = merkle_levels(leaves.clone());
+        let root = levels[levels.len() - 1][0];
+
+        assert_eq!(levels.len(), 4);
+        for (index, leaf) in leaves.iter().enumerate() {
+            let path = merkle_path(&levels, index);
+            assert_eq!(path.len(), 3);
+            assert_eq!(merkle_root(leaf, index as u64, &path), root);
+            verify_merkle_proof(
+                leaf,
+                &MerkleProof {
+                    root,
+                    index: index as u64,
+                    path,
+                },
+            );
+        }
+        assert_ne!(merkle_root(&leaves[0], 1, &merkle_path(&levels, 0)), root);
+
+        // a single leaf is its own root
+        let levels = merkle_levels(vec![leaves[0]]);
+        assert_eq!(
+            merkle_root(&leaves[0], 0, &merkle_path(&levels, 0)),
+            leaves[0]
+        );
+    }
+
+    fn non_membership_proof(levels: &[Vec<[u8; 32]>], low: u8, index: usize) -> NonMembershipProof {
+        let proof = |index: usize| MerkleProof {
+            root: levels[levels.len() - 1][0],
+            index: index as u64,
+            path: merkle_path(levels, index),
+        };
+
+        NonMembershipProof {
+            low: [low; 20],
+            low_proof: proof(index),
+            high: [low + 0x10; 20],
+            high_proof: proof(index + 1),
+        }
+    }
+
+    #[test]
+    fn test_verify_non_membership() {
+        let leaves = [0x00_u8, 0x10, 0x20, 0x30].map(|i| address_leaf(&[i; 20]));
+        let levels = merkle_levels(leaves.to_vec());
+
+        let root = verify_non_membership(&[0x15; 20], &non_membership_proof(&levels, 0x10, 1));
+        assert_eq!(root, levels[2][0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Address is not between the bracketing leaves.")]
+    fn test_verify_non_membership_member() {
+        let leaves = [0x00_u8, 0x10, 0x20, 0x30].map(|i| address_leaf(&[i; 20]));
+        let levels = merkle_levels(leaves.to_vec());
+
+        verify_non_membership(&[0x10; 20], &non_membership_proof(&levels, 0x10, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Bracketing leaves are not adjacent.")]
+    fn test_verify_non_membership_not_adjacent() {
+        let leaves = [0x00_u8, 0x10, 0x20, 0x30].map(|i| address_leaf(&[i; 20]));
+        let levels = merkle_levels(leaves.to_vec());
+        let mut proof = non_membership_proof(&levels, 0x10, 1);
+        // skip the leaf 0x20 to bracket it
+        proof.high = [0x30; 20];
+        proof.high_proof = non_membership_proof(&levels, 0x20, 2).high_proof;
+
+        verify_non_membership(&[0x20; 20], &proof);
+    }
+
+    #[test]
+    #[should_panic(expected = "Merkle index is outside of the tree.")]
+    fn test_merkle_root_index_outside() {
+        merkle_root(&[0_u8; 32], 2, &[[0_u8; 32]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Merkle proof.")]
+    fn test_verify_merkle_proof_invalid() {
+        let levels = merkle_levels(vec![[1_u8; 32], [2_u8; 32]]);
+        let proof = MerkleProof {
+            root: levels[1][0],
+            index: 0,
+            path: merkle_path(&levels, 0),
+        };
+        verify_merkle_proof(&[2_u8; 32], &proof);
+    }
+}
@@ -1,5 +1,95 @@
+use crate::eth_utils::SlotLayout;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+// Claim made about a balance, amounts are 32-byte big-endian words in base units (e.g. wei)
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum BalanceClaim {
+    // caller-chosen threshold: the balance is at least this amount
+    AtLeast([u8; 32]),
+    // upper bound: the balance is at most this amount (zero proves an empty or absent account)
+    AtMost([u8; 32]),
+    // the balance is exactly this amount
+    Equal([u8; 32]),
+    // the balance lies within these inclusive bounds
+    Range([u8; 32], [u8; 32]),
+    // public tier table: only the highest tier reached by the balance is committed
+    Tiers(Vec<[u8; 32]>),
+}
+
+// Committed result of a BalanceClaim
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum ProvenBalance {
+    AtLeast([u8; 32]),
+    AtMost([u8; 32]),
+    Equal([u8; 32]),
+    Range([u8; 32], [u8; 32]),
+    // the balance is at least `tier`, the highest reached tier of `tiers`
+    Tier {
+        tier: [u8; 32],
+        tiers: Vec<[u8; 32]>,
+    },
+}
+
+// Account field a predicate compares, every field is read as a 32-byte big-endian word
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum AccountField {
+    Nonce,
+    Balance,
+    StorageRoot,
+    CodeHash,
+}
+
+// Boolean predicate over the fields of an account, e.g. "nonce >= 100 AND balance >= 1 ETH"
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Predicate {
+    Geq(AccountField, [u8; 32]),
+    Leq(AccountField, [u8; 32]),
+    Eq(AccountField, [u8; 32]),
+    And(Vec<Predicate>),
+    Or(Vec<Predicate>),
+    Not(Box<Predicate>),
+}
+
+// Where a token records that an account is blacklisted, i.e. its balance is frozen
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum BlacklistLayout {
+    // mapping(address => bool) at this slot, e.g. USDT's isBlackListed
+    Mapping([u8; 32]),
+    // highest bit of the balance slot, e.g. USDC's balanceAndBlacklistStates since FiatToken v2.2
+    BalanceHighBit,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum BlacklistProof {
+    // Merkle Patricia trie proof for the holder's entry of the mapping at `blacklist_slot`
+    Mapping {
+        blacklist_slot: [u8; 32],
+        storage_proof: Vec<Vec<u8>>,
+    },
+    // the flag is read from the balance slot, which is already proven
+    BalanceHighBit,
+}
+
+// Inclusion proof of a leaf in a keccak Merkle tree
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MerkleProof {
+    // public root of the tree
+    pub root: [u8; 32],
+    // position of the leaf, whose bits select the side of each sibling
+    pub index: u64,
+    // sibling of each level, from the leaves up
+    pub path: Vec<[u8; 32]>,
+}
+
+// Non-membership proof in a tree of sorted address leaves: two adjacent leaves bracketing the address
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NonMembershipProof {
+    pub low: [u8; 20],
+    pub low_proof: MerkleProof,
+    pub high: [u8; 20],
+    pub high_proof: MerkleProof,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct NativeProofInput {
     // account address
@@ -10,19 +100,39 @@ pub struct NativeProofInput {
     pub block_hash: [u8; 32],
     // Merkle Patricia trie proof for provided account
     pub account_proof: Vec<Vec<u8>>,
-    // to prove that the account's balance is larger than some predefined number
-    pub expected_balance: u64,
+    // claim proven about the account's balance (threshold or tier table)
+    pub balance_claim: BalanceClaim,
     // used to prove ownership of the account
     pub signature: Vec<u8>,
     pub message: Vec<u8>,
+    // private salt for the optional address commitment
+    pub address_salt: Option<[u8; 32]>,
+    // private blinding factor for the optional balance commitment
+    pub balance_blinding: Option<[u8; 32]>,
+    // optional predicate the account must satisfy, on top of the balance claim
+    pub predicate: Option<Predicate>,
+    // optional proof that the address is in an address allowlist tree
+    pub address_allowlist: Option<MerkleProof>,
+    // optional proof that the address is not in a sorted sanctions tree
+    pub sanctions: Option<NonMembershipProof>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct NativeProofOutput {
     pub root: [u8; 32],
-    pub expected_balance: u64,
+    pub balance_claim: ProvenBalance,
     pub block_hash: [u8; 32],
     pub message: Vec<u8>,
+    // keccak(address || salt), allows later selective disclosure of the address
+    pub address_commitment: Option<[u8; 32]>,
+    // keccak(balance || blinding), allows later disclosure of the exact balance
+    pub balance_commitment: Option<[u8; 32]>,
+    // predicate satisfied by the account
+    pub predicate: Option<Predicate>,
+    // root of the address allowlist tree the address belongs to
+    pub address_allowlist_root: Option<[u8; 32]>,
+    // root of the sanctions tree the address is not in
+    pub sanctions_root: Option<[u8; 32]>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -31,37 +141,814 @@ pub struct ContractProofInput {
     pub contract_address: [u8; 20],
     // Balance mapping slot (padded to bytes32)
     pub balance_slot: [u8; 32],
+    // Storage layout of the compiler used by the contract
+    pub layout: SlotLayout,
     // Account address
     pub user_address: [u8; 20],
     // Ethereum account trie root
-    pub storage_hash: [u8; 32],
+    pub root: [u8; 32],
     // Ethereum block hash
     pub block_hash: [u8; 32],
-    // Merkle Patricia trie proof for provided account
+    // Merkle Patricia trie proof for the contract account, which binds its storage root and code
+    pub account_proof: Vec<Vec<u8>>,
+    // Merkle Patricia trie proof for provided account's balance slot
     pub storage_proof: Vec<Vec<u8>>,
-    // To prove that the account's balance is larger than some predefined number
-    pub expected_balance: u64,
+    // Claim proven about the account's balance (threshold or tier table)
+    pub balance_claim: BalanceClaim,
     // Used to prove ownership of the account
     pub signature: Vec<u8>,
     pub message: Vec<u8>,
+    // Private salt for the optional address commitment
+    pub address_salt: Option<[u8; 32]>,
+    // Private blinding factor for the optional balance commitment
+    pub balance_blinding: Option<[u8; 32]>,
+    // Where the token records frozen accounts, if it can freeze them
+    pub blacklist: Option<BlacklistProof>,
+    // Optional proof that the address is in an address allowlist tree
+    pub address_allowlist: Option<MerkleProof>,
+    // Optional proof that the address is not in a sorted sanctions tree
+    pub sanctions: Option<NonMembershipProof>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ContractProofOutput {
     pub contract_address: [u8; 20],
+    pub root: [u8; 32],
     pub storage_hash: [u8; 32],
-    pub expected_balance: u64,
+    // code hash of the contract, to tell the real token from a look-alike
+    pub code_hash: [u8; 32],
+    pub balance_claim: ProvenBalance,
     pub block_hash: [u8; 32],
     pub balance_slot: [u8; 32],
+    pub layout: SlotLayout,
     pub message: Vec<u8>,
+    // keccak(address || salt), allows later selective disclosure of the address
+    pub address_commitment: Option<[u8; 32]>,
+    // keccak(balance || blinding), allows later disclosure of the exact balance
+    pub balance_commitment: Option<[u8; 32]>,
+    // true if the holder is not blacklisted according to `blacklist`, false without a blacklist
+    pub not_frozen: bool,
+    pub blacklist: Option<BlacklistLayout>,
+    // root of the address allowlist tree the address belongs to
+    pub address_allowlist_root: Option<[u8; 32]>,
+    // root of the sanctions tree the address is not in
+    pub sanctions_root: Option<[u8; 32]>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NativeKeyProofInput {
+    // secp256k1 private key of the account, private to the guest and never committed
+    pub private_key: [u8; 32],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proof for the account derived from the private key
+    pub account_proof: Vec<Vec<u8>>,
+    // claim proven about the account's balance (threshold or tier table)
+    pub balance_claim: BalanceClaim,
+    // public context string, committed in place of a signed message
+    pub context: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NativeKeyProofOutput {
+    pub root: [u8; 32],
+    pub balance_claim: ProvenBalance,
+    pub block_hash: [u8; 32],
+    pub context: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AccountOwnership {
+    // account address
+    pub user_address: [u8; 20],
+    // Merkle Patricia trie proof for provided account
+    pub account_proof: Vec<Vec<u8>>,
+    // signature of the shared message by this account
+    pub signature: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AggregateProofInput {
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // accounts whose balances are summed, sorted by address
+    pub accounts: Vec<AccountOwnership>,
+    // claim proven about the accounts' total balance (threshold or tier table)
+    pub balance_claim: BalanceClaim,
+    // message signed by every account
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AggregateProofOutput {
+    pub root: [u8; 32],
+    pub balance_claim: ProvenBalance,
+    pub block_hash: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct InactivityProofInput {
+    // account address, private to the guest
+    pub user_address: [u8; 20],
+    // RLP-encoded headers of the start and end blocks, which bind the state roots to the block hashes
+    pub start_header: Vec<u8>,
+    pub end_header: Vec<u8>,
+    pub start_block_hash: [u8; 32],
+    pub end_block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the account at both blocks
+    pub start_account_proof: Vec<Vec<u8>>,
+    pub end_account_proof: Vec<Vec<u8>>,
+    // balance the account never dropped below, as a 32-byte big-endian word
+    pub expected_balance: [u8; 32],
+    // used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct InactivityProofOutput {
+    pub start_block_hash: [u8; 32],
+    pub end_block_hash: [u8; 32],
+    pub start_block_number: u64,
+    pub end_block_number: u64,
+    pub expected_balance: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct OwnerProofInput {
+    // Controlled contract
+    pub contract_address: [u8; 20],
+    // Address-typed slot holding the owner, e.g. Ownable's _owner or the EIP-1967 admin slot
+    pub owner_slot: [u8; 32],
+    // Owner address, private to the guest
+    pub user_address: [u8; 20],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the contract account and its owner slot
+    pub account_proof: Vec<Vec<u8>>,
+    pub storage_proof: Vec<Vec<u8>>,
+    // Used to prove ownership of the owner account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct OwnerProofOutput {
+    pub contract_address: [u8; 20],
+    pub owner_slot: [u8; 32],
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+// Asset whose balance is valued in USD
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum AssetProof {
+    // native balance, from the account proof of the user
+    Native {
+        account_proof: Vec<Vec<u8>>,
+    },
+    // token balance, from the token contract's account proof and the user's balance slot
+    Token {
+        contract_address: [u8; 20],
+        balance_slot: [u8; 32],
+        layout: SlotLayout,
+        account_proof: Vec<Vec<u8>>,
+        storage_proof: Vec<Vec<u8>>,
+    },
+}
+
+// Storage layout of a Chainlink OCR2 aggregator, which varies across versions
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AggregatorLayout {
+    // slot of the packed hot variables, holding the latest round ID
+    pub hot_vars_slot: [u8; 32],
+    // byte offset of the uint32 latest round ID in the hot variables, from the least significant end
+    pub round_id_offset: u8,
+    // slot of the round ID => Transmission { int192 answer; uint32 observationsTimestamp;
+    // uint32 transmissionTimestamp } mapping
+    pub transmissions_slot: [u8; 32],
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UsdProofInput {
+    // account address
+    pub user_address: [u8; 20],
+    // RLP-encoded block header, whose state root anchors every proof and whose timestamp dates the
+    // price
+    pub header: Vec<u8>,
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // proof of the valued balance
+    pub asset: AssetProof,
+    pub asset_decimals: u8,
+    // Chainlink aggregator (not its proxy) pricing the asset in USD
+    pub aggregator_address: [u8; 20],
+    pub aggregator_layout: AggregatorLayout,
+    pub feed_decimals: u8,
+    // Merkle Patricia trie proofs for the aggregator account, its hot variables and latest transmission
+    pub aggregator_proof: Vec<Vec<u8>>,
+    pub hot_vars_proof: Vec<Vec<u8>>,
+    pub transmission_proof: Vec<Vec<u8>>,
+    // maximum age of the price at the block, in seconds
+    pub max_age: u64,
+    // to prove that the balance is worth at least this many US dollars
+    pub usd_threshold: u64,
+    // used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UsdProofOutput {
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    // token whose balance was valued, none for the native asset
+    pub asset: Option<TokenSlot>,
+    pub asset_decimals: u8,
+    pub aggregator_address: [u8; 20],
+    pub feed_decimals: u8,
+    pub round_id: u32,
+    // timestamp of the round's transmission, at most `max_age` seconds before the block
+    pub updated_at: u32,
+    pub max_age: u64,
+    pub usd_threshold: u64,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SupplyShareProofInput {
+    // Token contract
+    pub contract_address: [u8; 20],
+    // Balance mapping slot (padded to bytes32)
+    pub balance_slot: [u8; 32],
+    pub layout: SlotLayout,
+    // Total supply slot (padded to bytes32)
+    pub total_supply_slot: [u8; 32],
+    // Account address
+    pub user_address: [u8; 20],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the token account, the balance slot and the total supply slot
+    pub account_proof: Vec<Vec<u8>>,
+    pub balance_proof: Vec<Vec<u8>>,
+    pub total_supply_proof: Vec<Vec<u8>>,
+    // Share of the total supply held, in basis points (1/10000)
+    pub bps: u16,
+    // Used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SupplyShareProofOutput {
+    pub contract_address: [u8; 20],
+    pub code_hash: [u8; 32],
+    pub balance_slot: [u8; 32],
+    pub layout: SlotLayout,
+    pub total_supply_slot: [u8; 32],
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub bps: u16,
+    pub message: Vec<u8>,
+}
+
+// Proof of the storage backing a vault's total assets
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum VaultAssetsProof {
+    // total assets kept in the vault's own storage, summed over one or more slots (e.g. idle and
+    // debt), each proven under the vault's storage root
+    Stored {
+        slots: Vec<[u8; 32]>,
+        storage_proofs: Vec<Vec<Vec<u8>>>,
+    },
+    // total assets taken as the vault's balance of the underlying token
+    Held {
+        underlying_address: [u8; 20],
+        balance_slot: [u8; 32],
+        layout: SlotLayout,
+        account_proof: Vec<Vec<u8>>,
+        storage_proof: Vec<Vec<u8>>,
+    },
+}
+
+// Public description of a token balance read by a proof, so that the verifier can check that the
+// balance was read where the token keeps it
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TokenSlot {
+    pub address: [u8; 20],
+    pub code_hash: [u8; 32],
+    pub balance_slot: [u8; 32],
+    pub layout: SlotLayout,
+}
+
+// Public description of the total assets storage, committed by the vault proof
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum VaultAssets {
+    Stored(Vec<[u8; 32]>),
+    Held(TokenSlot),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VaultProofInput {
+    // ERC-4626 vault, whose shares are an ERC-20 token
+    pub vault_address: [u8; 20],
+    // share balance mapping slot (padded to bytes32)
+    pub balance_slot: [u8; 32],
+    pub layout: SlotLayout,
+    // share total supply slot (padded to bytes32)
+    pub total_supply_slot: [u8; 32],
+    // account address
+    pub user_address: [u8; 20],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the vault account, the share balance and the total supply
+    pub account_proof: Vec<Vec<u8>>,
+    pub balance_proof: Vec<Vec<u8>>,
+    pub total_supply_proof: Vec<Vec<u8>>,
+    pub assets: VaultAssetsProof,
+    // decimals offset of OpenZeppelin's virtual shares, 0 for vaults without one
+    pub decimals_offset: u8,
+    // to prove that the shares are worth at least this amount of the underlying, in base units
+    pub threshold: [u8; 32],
+    // used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VaultProofOutput {
+    pub vault_address: [u8; 20],
+    pub code_hash: [u8; 32],
+    pub balance_slot: [u8; 32],
+    pub layout: SlotLayout,
+    pub total_supply_slot: [u8; 32],
+    pub assets: VaultAssets,
+    pub decimals_offset: u8,
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub threshold: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+// Storage layout of Lido's stETH, whose balances are shares of the total pooled ether. Apart from
+// the shares mapping, values sit at unstructured storage positions, keccak256 of a name such as
+// "lido.Lido.bufferedEther"
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LidoLayout {
+    // slot of the holder => shares mapping
+    pub shares_slot: [u8; 32],
+    pub total_shares_position: [u8; 32],
+    pub buffered_ether_position: [u8; 32],
+    pub cl_balance_position: [u8; 32],
+    pub deposited_validators_position: [u8; 32],
+    pub cl_validators_position: [u8; 32],
+}
+
+// Proof of a wstETH balance, which is an amount of stETH shares
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WrappedSharesProof {
+    pub wsteth_address: [u8; 20],
+    pub balance_slot: [u8; 32],
+    pub layout: SlotLayout,
+    pub account_proof: Vec<Vec<u8>>,
+    pub storage_proof: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StethProofInput {
+    // stETH token, i.e. the Lido contract
+    pub steth_address: [u8; 20],
+    pub lido_layout: LidoLayout,
+    // account address
+    pub user_address: [u8; 20],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the stETH account, the holder's shares, the total shares and
+    // the values making up the total pooled ether
+    pub account_proof: Vec<Vec<u8>>,
+    pub shares_proof: Vec<Vec<u8>>,
+    pub total_shares_proof: Vec<Vec<u8>>,
+    pub buffered_ether_proof: Vec<Vec<u8>>,
+    pub cl_balance_proof: Vec<Vec<u8>>,
+    pub deposited_validators_proof: Vec<Vec<u8>>,
+    pub cl_validators_proof: Vec<Vec<u8>>,
+    // wstETH balance, added to the holder's shares
+    pub wrapped: Option<WrappedSharesProof>,
+    // to prove that the shares are worth at least this amount of stETH, in wei
+    pub threshold: [u8; 32],
+    // used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StethProofOutput {
+    pub steth_address: [u8; 20],
+    pub code_hash: [u8; 32],
+    pub lido_layout: LidoLayout,
+    // wstETH token, if its balance was included
+    pub wrapped: Option<TokenSlot>,
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub threshold: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AaveProofInput {
+    // Aave v3 Pool, and the slot of its asset => ReserveData mapping
+    pub pool_address: [u8; 20],
+    pub reserves_slot: [u8; 32],
+    // underlying asset of the reserve
+    pub asset_address: [u8; 20],
+    // aToken, and the slot of its holder => UserState { uint128 balance; uint128 additionalData }
+    // mapping
+    pub a_token_address: [u8; 20],
+    pub user_state_slot: [u8; 32],
+    // account address
+    pub user_address: [u8; 20],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the aToken account and the holder's scaled balance
+    pub a_token_account_proof: Vec<Vec<u8>>,
+    pub balance_proof: Vec<Vec<u8>>,
+    // Merkle Patricia trie proofs for the Pool account and the reserve's liquidity index, last update
+    // timestamp and aToken address
+    pub pool_account_proof: Vec<Vec<u8>>,
+    pub liquidity_index_proof: Vec<Vec<u8>>,
+    pub last_update_proof: Vec<Vec<u8>>,
+    pub a_token_slot_proof: Vec<Vec<u8>>,
+    // to prove that the balance is at least this amount of the underlying, in base units
+    pub threshold: [u8; 32],
+    // used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AaveProofOutput {
+    pub pool_address: [u8; 20],
+    pub reserves_slot: [u8; 32],
+    pub asset_address: [u8; 20],
+    pub a_token_address: [u8; 20],
+    pub user_state_slot: [u8; 32],
+    // stored liquidity index (ray), and the timestamp it was last updated at
+    pub liquidity_index: [u8; 32],
+    pub last_update_timestamp: u64,
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub threshold: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+// Storage layout of a Uniswap v2 style pair, whose LP shares are an ERC-20 token
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PairLayout {
+    pub total_supply_slot: [u8; 32],
+    // slot of the holder => LP balance mapping
+    pub balance_slot: [u8; 32],
+    pub token0_slot: [u8; 32],
+    pub token1_slot: [u8; 32],
+    // slot packing uint112 reserve0, uint112 reserve1 and uint32 blockTimestampLast
+    pub reserves_slot: [u8; 32],
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LpProofInput {
+    pub pair_address: [u8; 20],
+    pub pair_layout: PairLayout,
+    // account address
+    pub user_address: [u8; 20],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the pair account, the LP balance, the total supply, both
+    // token addresses and the reserves
+    pub account_proof: Vec<Vec<u8>>,
+    pub balance_proof: Vec<Vec<u8>>,
+    pub total_supply_proof: Vec<Vec<u8>>,
+    pub token0_proof: Vec<Vec<u8>>,
+    pub token1_proof: Vec<Vec<u8>>,
+    pub reserves_proof: Vec<Vec<u8>>,
+    // to prove that the position is worth at least these amounts of token0 and token1, in base units
+    pub threshold0: [u8; 32],
+    pub threshold1: [u8; 32],
+    // used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LpProofOutput {
+    pub pair_address: [u8; 20],
+    pub code_hash: [u8; 32],
+    pub pair_layout: PairLayout,
+    pub token0: [u8; 20],
+    pub token1: [u8; 20],
+    // timestamp of the block the reserves were last updated in
+    pub reserves_timestamp: u32,
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub threshold0: [u8; 32],
+    pub threshold1: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+// Storage layout of a Compound v2 cToken
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CTokenLayout {
+    pub total_borrows_slot: [u8; 32],
+    pub total_reserves_slot: [u8; 32],
+    pub total_supply_slot: [u8; 32],
+    // slot of the holder => cToken balance mapping (accountTokens)
+    pub balance_slot: [u8; 32],
+    // slot of the underlying token's address, unused by cETH
+    pub underlying_slot: [u8; 32],
+    // slot of initialExchangeRateMantissa, the exchange rate of a market without cTokens
+    pub initial_exchange_rate_slot: [u8; 32],
+}
+
+// Proof of a cToken's cash, the underlying it holds
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum CashProof {
+    // cETH holds ether, its cash is the balance of its own account
+    Native,
+    // any other cToken holds its underlying token, whose address it stores
+    Token {
+        underlying_proof: Vec<Vec<u8>>,
+        underlying_address: [u8; 20],
+        balance_slot: [u8; 32],
+        layout: SlotLayout,
+        account_proof: Vec<Vec<u8>>,
+        storage_proof: Vec<Vec<u8>>,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CTokenProofInput {
+    pub ctoken_address: [u8; 20],
+    pub ctoken_layout: CTokenLayout,
+    // account address
+    pub user_address: [u8; 20],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the cToken account, the holder's balance, the total borrows,
+    // reserves and supply, and the initial exchange rate
+    pub account_proof: Vec<Vec<u8>>,
+    pub balance_proof: Vec<Vec<u8>>,
+    pub total_borrows_proof: Vec<Vec<u8>>,
+    pub total_reserves_proof: Vec<Vec<u8>>,
+    pub total_supply_proof: Vec<Vec<u8>>,
+    pub initial_exchange_rate_proof: Vec<Vec<u8>>,
+    pub cash: CashProof,
+    // to prove that the cTokens are worth at least this amount of the underlying, in base units
+    pub threshold: [u8; 32],
+    // used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CTokenProofOutput {
+    pub ctoken_address: [u8; 20],
+    pub code_hash: [u8; 32],
+    pub ctoken_layout: CTokenLayout,
+    // underlying token, none for ether
+    pub underlying: Option<TokenSlot>,
+    // stored exchange rate, scaled by 10^18
+    pub exchange_rate: [u8; 32],
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub threshold: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VotesProofInput {
+    // ERC20Votes token
+    pub token_address: [u8; 20],
+    // slot of the account => Checkpoint[] mapping
+    pub checkpoints_slot: [u8; 32],
+    // size of the checkpoint key packed below the votes: 4 bytes (uint32 fromBlock) up to
+    // OpenZeppelin 4, 6 bytes (uint48 timepoint) from OpenZeppelin 5
+    pub key_bytes: u8,
+    // account address
+    pub user_address: [u8; 20],
+    // Ethereum account trie root
+    pub root: [u8; 32],
+    // Ethereum block hash
+    pub block_hash: [u8; 32],
+    // Merkle Patricia trie proofs for the token account, the checkpoints array length and its last
+    // element (unused if the array is empty)
+    pub account_proof: Vec<Vec<u8>>,
+    pub length_proof: Vec<Vec<u8>>,
+    pub checkpoint_proof: Vec<Vec<u8>>,
+    // to prove that the account's voting power is at least this amount
+    pub threshold: [u8; 32],
+    // used to prove ownership of the account
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VotesProofOutput {
+    pub token_address: [u8; 20],
+    pub code_hash: [u8; 32],
+    pub checkpoints_slot: [u8; 32],
+    pub key_bytes: u8,
+    // block number or timestamp of the last checkpoint, none without checkpoints
+    pub checkpoint_key: Option<u64>,
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub threshold: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+// How a voter proves that they own the weighted account, which also derives their nullifier
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum VoteWitness {
+    // secp256k1 private key of the voter, private to the guest and never committed
+    PrivateKey([u8; 32]),
+    // signature of the vote message of the poll and choice, for wallets that cannot export their key
+    Signature {
+        user_address: [u8; 20],
+        signature: Vec<u8>,
+        message: Vec<u8>,
+    },
+}
+
+// Secret a vote's nullifier is derived from. A key's holder could vote once with each, so that a
+// tally only counts votes of one kind.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum NullifierKind {
+    // keccak(tag || private key || poll ID), which nobody else can link to the address
+    PrivateKey,
+    // keccak(tag || address || poll ID), which anyone who guesses the address can recompute
+    Address,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VoteProofInput {
+    pub witness: VoteWitness,
+    // RLP-encoded header of the poll's snapshot block, whose state root anchors the balance proof
+    pub header: Vec<u8>,
+    // Ethereum block hash of the snapshot block
+    pub block_hash: [u8; 32],
+    // proof of the balance weighting the vote
+    pub asset: AssetProof,
+    pub poll_id: [u8; 32],
+    pub choice: u32,
+    // public weight tiers, the exact balance weights the vote if none
+    pub tiers: Option<Vec<[u8; 32]>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct VoteProofOutput {
+    pub root: [u8; 32],
+    pub block_hash: [u8; 32],
+    // token whose balance weights the vote, none for the native asset
+    pub asset: Option<TokenSlot>,
+    pub poll_id: [u8; 32],
+    pub choice: u32,
+    // exact balance, or highest tier reached
+    pub weight: ProvenBalance,
+    // rejects a second vote of the same voter
+    pub nullifier: [u8; 32],
+    pub nullifier_kind: NullifierKind,
 }
 
 pub trait ProofInput: Serialize {}
 
 impl ProofInput for ContractProofInput {}
 impl ProofInput for NativeProofInput {}
+impl ProofInput for NativeKeyProofInput {}
+impl ProofInput for AggregateProofInput {}
+impl ProofInput for InactivityProofInput {}
+impl ProofInput for OwnerProofInput {}
+impl ProofInput for UsdProofInput {}
+impl ProofInput for SupplyShareProofInput {}
+impl ProofInput for VaultProofInput {}
+impl ProofInput for StethProofInput {}
+impl ProofInput for AaveProofInput {}
+impl ProofInput for LpProofInput {}
+impl ProofInput for CTokenProofInput {}
+impl ProofInput for VotesProofInput {}
+impl ProofInput for VoteProofInput {}
 
-pub trait ProofOutput: Serialize + DeserializeOwned + Sized {}
+pub trait ProofOutput: Serialize + DeserializeOwned + Sized {
+    // signed message committed by the proof, checked against a challenge by the verifier
+    fn message(&self) -> Option<&[u8]> {
+        None
+    }
+
+    // unsigned context committed instead of a message, checked against a challenge by the verifier
+    fn context(&self) -> Option<&[u8]> {
+        None
+    }
+}
+
+impl ProofOutput for ContractProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for NativeProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for NativeKeyProofOutput {
+    fn context(&self) -> Option<&[u8]> {
+        Some(&self.context)
+    }
+}
+
+impl ProofOutput for AggregateProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for InactivityProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for OwnerProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for UsdProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for SupplyShareProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for VaultProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for StethProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for AaveProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for LpProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for CTokenProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
+
+impl ProofOutput for VotesProofOutput {
+    fn message(&self) -> Option<&[u8]> {
+        Some(&self.message)
+    }
+}
 
-impl ProofOutput for ContractProofOutput {}
-impl ProofOutput for NativeProofOutput {}
+impl ProofOutput for VoteProofOutput {}
@@ -1,5 +1,13 @@
-use crate::eth_utils::{derive_address, recover_public_key};
-use eth_trie::{EthTrie, MemoryDB};
+use crate::eth_utils::{
+    decode_account, decode_block_header, derive_address, mapping_slot, packed_field,
+    recover_public_key, to_word, word_mapping_slot, Account, BlockHeader, SlotLayout,
+};
+use crate::proof_io::{
+    AccountField, AggregatorLayout, AssetProof, BalanceClaim, Predicate, ProvenBalance, TokenSlot,
+};
+use eth_trie::{EthTrie, MemoryDB, Trie};
+use primitive_types::U256;
+use sha3::{Digest, Keccak256};
 use std::sync::Arc;
 
 pub fn verify_signed_message(signature: &Vec<u8>, message: &Vec<u8>, user_address: &[u8]) {
@@ -9,6 +17,395 @@ pub fn verify_signed_message(signature: &Vec<u8>, message: &Vec<u8>, user_addres
     }
 }
 
+pub fn check_balance_claim(balance: &[u8], claim: BalanceClaim) -> ProvenBalance {
+    let balance = U256::from_big_endian(balance);
+    match claim {
+        BalanceClaim::AtLeast(expected_balance) => {
+            if balance < U256::from_big_endian(&expected_balance) {
+                panic!("Account balance is smaller than the expected balance.");
+            }
+            ProvenBalance::AtLeast(expected_balance)
+        }
+        BalanceClaim::AtMost(expected_balance) => {
+            if balance > U256::from_big_endian(&expected_balance) {
+                panic!("Account balance is larger than the expected balance.");
+            }
+            ProvenBalance::AtMost(expected_balance)
+        }
+        BalanceClaim::Equal(expected_balance) => {
+            if balance != U256::from_big_endian(&expected_balance) {
+                panic!("Account balance does not equal the expected balance.");
+            }
+            ProvenBalance::Equal(expected_balance)
+        }
+        BalanceClaim::Range(min_balance, max_balance) => {
+            if balance < U256::from_big_endian(&min_balance)
+                || balance > U256::from_big_endian(&max_balance)
+            {
+                panic!("Account balance is outside of the expected range.");
+            }
+            ProvenBalance::Range(min_balance, max_balance)
+        }
+        BalanceClaim::Tiers(tiers) => {
+            // Highest tier of the table that the balance reaches, whatever the table's order
+            let tier = tiers
+                .iter()
+                .map(|tier| U256::from_big_endian(tier))
+                .filter(|tier| balance >= *tier)
+                .max()
+                .expect("Account balance is smaller than the lowest tier.");
+            let mut tier_bytes = [0_u8; 32];
+            tier.to_big_endian(&mut tier_bytes);
+            ProvenBalance::Tier {
+                tier: tier_bytes,
+                tiers,
+            }
+        }
+    }
+}
+
+// Verifies an account proof against the state root and returns the decoded account. A valid
+// proof of absence yields an empty account (zero nonce and balance), as it has never been touched.
+pub fn verify_account(root: &[u8; 32], address: &[u8; 20], account_proof: Vec<Vec<u8>>) -> Account {
+    let trie = create_eth_trie();
+    let key = Keccak256::digest(address).to_vec();
+    let account = trie
+        .verify_proof(root.into(), &key, account_proof)
+        .expect("Invalid account proof.");
+
+    match account {
+        Some(account) => decode_account(&account).expect("Invalid account encoding."),
+        None => Account::empty(),
+    }
+}
+
+// Checks that the RLP-encoded header hashes to the block hash, and returns it decoded so that its
+// state root can anchor account proofs
+pub fn verify_block_header(header: &[u8], block_hash: &[u8; 32]) -> BlockHeader {
+    if Keccak256::digest(header).as_slice() != block_hash {
+        panic!("Block header does not match the block hash.");
+    }
+
+    decode_block_header(header).expect("Invalid block header encoding.")
+}
+
+// Evaluates a predicate over the fields of an account
+pub fn evaluate_predicate(account: &Account, predicate: &Predicate) -> bool {
+    let field_value = |field: &AccountField| match field {
+        AccountField::Nonce => U256::from(account.nonce),
+        AccountField::Balance => U256::from_big_endian(&account.balance),
+        AccountField::StorageRoot => U256::from_big_endian(&account.storage_root),
+        AccountField::CodeHash => U256::from_big_endian(&account.code_hash),
+    };
+
+    match predicate {
+        Predicate::Geq(field, value) => field_value(field) >= U256::from_big_endian(value),
+        Predicate::Leq(field, value) => field_value(field) <= U256::from_big_endian(value),
+        Predicate::Eq(field, value) => field_value(field) == U256::from_big_endian(value),
+        Predicate::And(predicates) => predicates.iter().all(|p| evaluate_predicate(account, p)),
+        Predicate::Or(predicates) => predicates.iter().any(|p| evaluate_predicate(account, p)),
+        Predicate::Not(predicate) => !evaluate_predicate(account, predicate),
+    }
+}
+
+// Verifies a storage proof against the storage root and returns the slot's value. Slots are
+// stored RLP-encoded, and a valid proof of absence yields an empty (zero) value.
+pub fn verify_storage_value(
+    storage_hash: &[u8; 32],
+    key: &[u8],
+    storage_proof: Vec<Vec<u8>>,
+) -> Vec<u8> {
+    let trie = create_eth_trie();
+    let value = trie
+        .verify_proof(storage_hash.into(), key, storage_proof)
+        .expect("Invalid storage proof.");
+
+    match value {
+        Some(value) => rlp::decode(&value).unwrap(),
+        None => vec![],
+    }
+}
+
+// Verifies a contract's account proof against the state root, then the proof of one of its storage
+// slots against the contract's storage root. Returns the contract account and the slot's value.
+pub fn verify_contract_storage(
+    root: &[u8; 32],
+    contract_address: &[u8; 20],
+    account_proof: Vec<Vec<u8>>,
+    slot: &[u8; 32],
+    storage_proof: Vec<Vec<u8>>,
+) -> (Account, Vec<u8>) {
+    let account = verify_account(root, contract_address, account_proof);
+    let key = Keccak256::digest(slot).to_vec();
+    let value = verify_storage_value(&account.storage_root, &key, storage_proof);
+
+    (account, value)
+}
+
+// Verifies a native or token balance under the state root. Returns the token whose balance was
+// read, none for the native asset, and the balance.
+pub fn verify_asset_balance(
+    root: &[u8; 32],
+    user_address: &[u8; 20],
+    asset: AssetProof,
+) -> (Option<TokenSlot>, Vec<u8>) {
+    match asset {
+        AssetProof::Native { account_proof } => (
+            None,
+            verify_account(root, user_address, account_proof)
+                .balance
+                .to_vec(),
+        ),
+        AssetProof::Token {
+            contract_address,
+            balance_slot,
+            layout,
+            account_proof,
+            storage_proof,
+        } => {
+            let slot = mapping_slot(layout, user_address, &balance_slot);
+            let (token, balance) = verify_contract_storage(
+                root,
+                &contract_address,
+                account_proof,
+                &slot,
+                storage_proof,
+            );
+            let token = TokenSlot {
+                address: contract_address,
+                code_hash: token.code_hash,
+                balance_slot,
+                layout,
+            };
+            (Some(token), balance)
+        }
+    }
+}
+
+// Reads an address-typed slot value, held in its lowest 20 bytes
+pub fn slot_to_address(value: &[u8]) -> [u8; 20] {
+    let mut word = [0_u8; 32];
+    if value.len() > 32 {
+        panic!("Storage value is larger than a word.");
+    }
+    word[32 - value.len()..].copy_from_slice(value);
+    word[12..].try_into().unwrap()
+}
+
+// Splits a balance slot whose highest bit is the blacklist flag, e.g. USDC's
+// balanceAndBlacklistStates, into the balance and the flag. Slot values come without their
+// leading zero bytes, so the flag can only be set in a full word.
+pub fn split_balance_high_bit(value: &[u8]) -> (Vec<u8>, bool) {
+    let mut balance = value.to_vec();
+    let blacklisted = balance.len() == 32 && balance[0] & 0x80 != 0;
+    if blacklisted {
+        balance[0] &= 0x7f;
+    }
+
+    (balance, blacklisted)
+}
+
+// Latest round of a Chainlink aggregator
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainlinkRound {
+    pub round_id: u32,
+    pub answer: U256,
+    pub updated_at: u32,
+}
+
+// Verifies the aggregator's account proof under the state root, then its hot variables holding the
+// latest round ID, then that round's transmission holding the answer and its timestamp
+pub fn verify_chainlink_round(
+    root: &[u8; 32],
+    aggregator_address: &[u8; 20],
+    layout: &AggregatorLayout,
+    aggregator_proof: Vec<Vec<u8>>,
+    hot_vars_proof: Vec<Vec<u8>>,
+    transmission_proof: Vec<Vec<u8>>,
+) -> ChainlinkRound {
+    let aggregator = verify_account(root, aggregator_address, aggregator_proof);
+
+    let hot_vars = verify_storage_value(
+        &aggregator.storage_root,
+        &Keccak256::digest(layout.hot_vars_slot),
+        hot_vars_proof,
+    );
+    let round_id = packed_field(&hot_vars, layout.round_id_offset as usize, 4).as_u32();
+
+    let mut round_key = [0_u8; 32];
+    round_key[28..].copy_from_slice(&round_id.to_be_bytes());
+    let transmission_slot =
+        word_mapping_slot(SlotLayout::Solidity, &round_key, &layout.transmissions_slot);
+    let transmission = verify_storage_value(
+        &aggregator.storage_root,
+        &Keccak256::digest(transmission_slot),
+        transmission_proof,
+    );
+
+    // int192 answer, only positive prices can value an asset
+    let answer = packed_field(&transmission, 0, 24);
+    if answer.is_zero() || answer.bit(191) {
+        panic!("Oracle answer is not positive.");
+    }
+
+    ChainlinkRound {
+        round_id,
+        answer,
+        updated_at: packed_field(&transmission, 28, 4).as_u32(),
+    }
+}
+
+// Checks that the round was updated at most `max_age` seconds before the block it is read at, so
+// that a stale price cannot value a balance
+pub fn check_round_age(round: &ChainlinkRound, block_timestamp: u64, max_age: u64) {
+    if (round.updated_at as u64).saturating_add(max_age) < block_timestamp {
+        panic!("Oracle answer is older than the maximum age.");
+    }
+}
+
+// Checks that balance * answer, scaled down by the asset's and the feed's decimals, is at least
+// the USD threshold. Both sides are compared at full width so that nothing overflows.
+pub fn verify_usd_value(
+    balance: &[u8],
+    answer: U256,
+    asset_decimals: u8,
+    feed_decimals: u8,
+    usd_threshold: u64,
+) {
+    let decimals = asset_decimals as usize + feed_decimals as usize;
+    if decimals > 77 {
+        panic!("Too many decimals.");
+    }
+
+    let value = U256::from_big_endian(balance).full_mul(answer);
+    let threshold = U256::from(usd_threshold).full_mul(U256::exp10(decimals));
+    if value < threshold {
+        panic!("Account balance is worth less than the USD threshold.");
+    }
+}
+
+// Checks that balance / total_supply >= bps / 10000, compared at full width so that nothing overflows
+pub fn verify_supply_share(balance: &[u8], total_supply: &[u8], bps: u16) {
+    if bps > 10000 {
+        panic!("Basis points cannot exceed 10000.");
+    }
+
+    let held = U256::from_big_endian(balance).full_mul(U256::from(10000));
+    let required = U256::from_big_endian(total_supply).full_mul(U256::from(bps));
+    if held < required {
+        panic!("Account holds less than the claimed share of the total supply.");
+    }
+}
+
+// Sums storage words, panicking rather than wrapping on overflow
+pub fn sum_storage_values(values: &[Vec<u8>]) -> [u8; 32] {
+    let sum = values.iter().fold(U256::zero(), |sum, value| {
+        sum.checked_add(U256::from_big_endian(value))
+            .expect("Sum of storage values overflows.")
+    });
+
+    to_word(sum)
+}
+
+// Converts shares into the underlying amount, shares * total_assets / total_shares rounded down so
+// that the holding is never overstated
+pub fn convert_to_assets(shares: &[u8], total_assets: &[u8], total_shares: &[u8]) -> [u8; 32] {
+    let total_shares = U256::from_big_endian(total_shares);
+    if total_shares.is_zero() {
+        panic!("Total shares cannot be zero.");
+    }
+
+    let assets = U256::from_big_endian(shares).full_mul(U256::from_big_endian(total_assets))
+        / total_shares.full_mul(U256::one());
+    to_word(U256::try_from(assets).expect("Underlying amount overflows."))
+}
+
+// Converts vault shares into assets for a vault that may have OpenZeppelin's virtual shares and
+// assets, which pays shares * (total_assets + 1) / (total_supply + 10^decimals_offset). The lower of
+// that and the plain conversion is returned, so that the holding is never overstated whichever
+// formula the vault uses.
+pub fn convert_vault_shares(
+    shares: &[u8],
+    total_assets: &[u8],
+    total_supply: &[u8],
+    decimals_offset: u8,
+) -> [u8; 32] {
+    let virtual_assets = U256::from_big_endian(total_assets)
+        .checked_add(U256::one())
+        .expect("Total assets overflow.");
+    let virtual_supply = U256::from(10)
+        .checked_pow(U256::from(decimals_offset))
+        .and_then(|virtual_shares| virtual_shares.checked_add(U256::from_big_endian(total_supply)))
+        .expect("Total supply overflows.");
+    let assets = convert_to_assets(shares, &to_word(virtual_assets), &to_word(virtual_supply));
+
+    // an empty vault only has its virtual shares
+    if U256::from_big_endian(total_supply).is_zero() {
+        return assets;
+    }
+    assets.min(convert_to_assets(shares, total_assets, total_supply))
+}
+
+// Lido's total pooled ether: the buffered ether, the consensus layer balance, and 32 ether for each
+// validator deposited but not yet seen on the consensus layer
+pub fn lido_total_pooled_ether(
+    buffered_ether: &[u8],
+    cl_balance: &[u8],
+    deposited_validators: &[u8],
+    cl_validators: &[u8],
+) -> [u8; 32] {
+    let transient_validators = U256::from_big_endian(deposited_validators)
+        .checked_sub(U256::from_big_endian(cl_validators))
+        .expect("Lido reports more validators than were deposited.");
+    let transient_balance = transient_validators
+        .checked_mul(U256::exp10(18) * 32)
+        .expect("Sum of storage values overflows.");
+
+    sum_storage_values(&[
+        buffered_ether.to_vec(),
+        cl_balance.to_vec(),
+        to_word(transient_balance).to_vec(),
+    ])
+}
+
+// Aave's rayMul of an amount by an index expressed in rays (10^27), rounded down rather than half up
+// so that the balance is never overstated
+pub fn ray_mul_down(amount: &[u8], index: &[u8]) -> [u8; 32] {
+    convert_to_assets(amount, index, &to_word(U256::exp10(27)))
+}
+
+// Compound's stored exchange rate, (cash + borrows - reserves) * 10^18 / supply rounded down, or
+// the initial exchange rate while there are no cTokens
+pub fn compound_exchange_rate(
+    cash: &[u8],
+    total_borrows: &[u8],
+    total_reserves: &[u8],
+    total_supply: &[u8],
+    initial_exchange_rate: &[u8],
+) -> [u8; 32] {
+    if U256::from_big_endian(total_supply).is_zero() {
+        return to_word(U256::from_big_endian(initial_exchange_rate));
+    }
+
+    let total_assets = U256::from_big_endian(&sum_storage_values(&[
+        cash.to_vec(),
+        total_borrows.to_vec(),
+    ]))
+    .checked_sub(U256::from_big_endian(total_reserves))
+    .expect("Reserves exceed the cash and borrows.");
+
+    convert_to_assets(
+        &to_word(U256::exp10(18)),
+        &to_word(total_assets),
+        total_supply,
+    )
+}
+
+// Underlying amount of cTokens at a stored exchange rate, rounded down as in balanceOfUnderlying
+pub fn compound_underlying(balance: &[u8], exchange_rate: &[u8]) -> [u8; 32] {
+    convert_to_assets(balance, exchange_rate, &to_word(U256::exp10(18)))
+}
+
 pub fn create_eth_trie() -> EthTrie<MemoryDB> {
     let memdb = Arc::new(MemoryDB::new(true));
     let trie = EthTrie::new(memdb.clone());
@@ -18,7 +415,7 @@ pub fn create_eth_trie() -> EthTrie<MemoryDB> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use eth_trie::Trie;
+    use crate::eth_utils::EMPTY_CODE_HASH;
     use prefix_hex::decode;
     use primitive_types::H256;
 
@@ -53,6 +450,358 @@ mod tests {
         verify_signed_message(&sig, &msg.to_vec(), &user_address);
     }
 
+    fn amount(value: u64) -> [u8; 32] {
+        let mut bytes = [0_u8; 32];
+        U256::from(value).to_big_endian(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn test_check_balance_claim_at_least() {
+        assert_eq!(
+            check_balance_claim(&[1, 0], BalanceClaim::AtLeast(amount(256))),
+            ProvenBalance::AtLeast(amount(256))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Account balance is smaller than the expected balance.")]
+    fn test_check_balance_claim_at_least_insufficient() {
+        check_balance_claim(&[255], BalanceClaim::AtLeast(amount(256)));
+    }
+
+    #[test]
+    fn test_verify_usd_value() {
+        // 1.5 ETH at $2000.12345678
+        let balance = amount(1_500_000_000_000_000_000);
+        let answer = U256::from(200_012_345_678_u64);
+
+        verify_usd_value(&balance, answer, 18, 8, 3000);
+        verify_usd_value(&[], answer, 18, 8, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account balance is worth less than the USD threshold.")]
+    fn test_verify_usd_value_below_threshold() {
+        let balance = amount(1_500_000_000_000_000_000);
+        verify_usd_value(&balance, U256::from(200_012_345_678_u64), 18, 8, 3001);
+    }
+
+    #[test]
+    fn test_check_round_age() {
+        let round = ChainlinkRound {
+            round_id: 1,
+            answer: U256::from(200_012_345_678_u64),
+            updated_at: 1_681_000_000,
+        };
+
+        check_round_age(&round, 1_681_003_600, 3600);
+        check_round_age(&round, 1_680_999_988, 0);
+        check_round_age(&round, u64::MAX, u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Oracle answer is older than the maximum age.")]
+    fn test_check_round_age_stale() {
+        let round = ChainlinkRound {
+            round_id: 1,
+            answer: U256::one(),
+            updated_at: 1_681_000_000,
+        };
+        check_round_age(&round, 1_681_003_601, 3600);
+    }
+
+    #[test]
+    fn test_verify_supply_share() {
+        // 500 out of 10000 is exactly 5%
+        verify_supply_share(&amount(500), &amount(10000), 500);
+        let max = [0xff_u8; 32];
+        verify_supply_share(&max, &max, 10000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account holds less than the claimed share of the total supply.")]
+    fn test_verify_supply_share_too_small() {
+        verify_supply_share(&amount(499), &amount(10000), 500);
+    }
+
+    #[test]
+    fn test_sum_storage_values() {
+        assert_eq!(sum_storage_values(&[]), [0_u8; 32]);
+        assert_eq!(
+            sum_storage_values(&[amount(2).to_vec(), vec![], vec![3]]),
+            amount(5)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Sum of storage values overflows.")]
+    fn test_sum_storage_values_overflow() {
+        sum_storage_values(&[vec![0xff_u8; 32], vec![1]]);
+    }
+
+    #[test]
+    fn test_convert_to_assets() {
+        // 1000 shares of a 3000 shares vault holding 10000 assets are worth 3333.33, rounded down
+        assert_eq!(
+            convert_to_assets(&amount(1000), &amount(10000), &amount(3000)),
+            amount(3333)
+        );
+        let max = [0xff_u8; 32];
+        assert_eq!(convert_to_assets(&max, &max, &max), max);
+        assert_eq!(convert_to_assets(&[], &max, &max), [0_u8; 32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Total shares cannot be zero.")]
+    fn test_convert_to_assets_no_shares() {
+        convert_to_assets(&amount(1), &amount(1), &[]);
+    }
+
+    #[test]
+    fn test_convert_vault_shares() {
+        // after a donation, 1000 shares of a vault with a decimals offset of 3 are worth
+        // 1000 * 1001 / 2000 assets, not the naive 1000
+        assert_eq!(
+            convert_to_assets(&amount(1000), &amount(1000), &amount(1000)),
+            amount(1000)
+        );
+        assert_eq!(
+            convert_vault_shares(&amount(1000), &amount(1000), &amount(1000), 3),
+            amount(500)
+        );
+        // a small supply without an offset: 1 * 11 / 2 rather than 10
+        assert_eq!(
+            convert_vault_shares(&amount(1), &amount(10), &amount(1), 0),
+            amount(5)
+        );
+        // the lower of 1000 * 10000 / 3000 and 1000 * 10001 / 3001
+        assert_eq!(
+            convert_vault_shares(&amount(1000), &amount(10000), &amount(3000), 0),
+            amount(3332)
+        );
+        assert_eq!(convert_vault_shares(&[], &[], &[], 6), [0_u8; 32]);
+    }
+
+    #[test]
+    fn test_lido_total_pooled_ether() {
+        // 1 buffered ether, 64 ether on 2 validators and 1 validator still in transit
+        let ether = U256::exp10(18);
+        let mut buffered_ether = [0_u8; 32];
+        ether.to_big_endian(&mut buffered_ether);
+        let mut cl_balance = [0_u8; 32];
+        (ether * U256::from(64)).to_big_endian(&mut cl_balance);
+        let mut expected = [0_u8; 32];
+        (ether * U256::from(97)).to_big_endian(&mut expected);
+
+        assert_eq!(
+            lido_total_pooled_ether(&buffered_ether, &cl_balance, &amount(3), &amount(2)),
+            expected
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Lido reports more validators than were deposited.")]
+    fn test_lido_total_pooled_ether_validators() {
+        lido_total_pooled_ether(&[], &[], &amount(2), &amount(3));
+    }
+
+    #[test]
+    fn test_ray_mul_down() {
+        // a scaled balance of 1000.000001 at an index of 1.05 is worth 1050.00000105, rounded down
+        let mut index = [0_u8; 32];
+        (U256::exp10(25) * U256::from(105)).to_big_endian(&mut index);
+
+        assert_eq!(
+            ray_mul_down(&amount(1_000_000_001), &index),
+            amount(1_050_000_001)
+        );
+    }
+
+    #[test]
+    fn test_compound_exchange_rate() {
+        // 900 cash and 200 borrows, less 100 reserves, for 50000 cTokens: 0.02 per cToken
+        let exchange_rate = compound_exchange_rate(
+            &amount(900),
+            &amount(200),
+            &amount(100),
+            &amount(50000),
+            &amount(1),
+        );
+
+        assert_eq!(exchange_rate, to_word(U256::exp10(16) * U256::from(2)));
+        assert_eq!(
+            compound_underlying(&amount(1249), &exchange_rate),
+            amount(24)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Reserves exceed the cash and borrows.")]
+    fn test_compound_exchange_rate_reserves() {
+        compound_exchange_rate(&amount(1), &amount(1), &amount(3), &amount(1), &amount(1));
+    }
+
+    #[test]
+    fn test_compound_exchange_rate_empty_market() {
+        // without cTokens, the market is at its initial exchange rate of 0.02
+        let initial_exchange_rate = to_word(U256::exp10(16) * U256::from(2));
+        let exchange_rate =
+            compound_exchange_rate(&amount(5), &[], &[], &[], &initial_exchange_rate);
+
+        assert_eq!(exchange_rate, initial_exchange_rate);
+        assert_eq!(compound_underlying(&[], &exchange_rate), amount(0));
+    }
+
+    #[test]
+    fn test_slot_to_address() {
+        let address = [0xab_u8; 20];
+        let mut packed = vec![0x01_u8];
+        packed.extend_from_slice(&address);
+
+        assert_eq!(slot_to_address(&address), address);
+        assert_eq!(slot_to_address(&packed), address);
+        assert_eq!(slot_to_address(&[]), [0_u8; 20]);
+    }
+
+    #[test]
+    fn test_split_balance_high_bit() {
+        // a full balance word below the highest bit
+        assert_eq!(
+            split_balance_high_bit(&[0x7f_u8; 32]),
+            ([0x7f_u8; 32].to_vec(), false)
+        );
+        assert_eq!(
+            split_balance_high_bit(&[0xff_u8; 31]),
+            ([0xff_u8; 31].to_vec(), false)
+        );
+        assert_eq!(split_balance_high_bit(&[]), (vec![], false));
+
+        // a frozen account proves its balance, not 2^255 + balance
+        let mut frozen = [0_u8; 32];
+        frozen[0] = 0x80;
+        frozen[31] = 5;
+        let (balance, blacklisted) = split_balance_high_bit(&frozen);
+        assert!(blacklisted);
+        assert_eq!(U256::from_big_endian(&balance), U256::from(5));
+    }
+
+    #[test]
+    fn test_verify_block_header() {
+        let mut stream = rlp::RlpStream::new_list(15);
+        stream.append(&vec![1_u8; 32]);
+        stream.append(&vec![2_u8; 32]);
+        stream.append(&vec![3_u8; 20]);
+        stream.append(&vec![4_u8; 32]);
+        for i in 4..15_u64 {
+            stream.append(&i);
+        }
+        let header = stream.out().to_vec();
+        let block_hash: [u8; 32] = Keccak256::digest(&header).into();
+
+        let decoded = verify_block_header(&header, &block_hash);
+        assert_eq!(decoded.state_root, [4_u8; 32]);
+        assert_eq!(decoded.number, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Block header does not match the block hash.")]
+    fn test_verify_block_header_wrong_hash() {
+        verify_block_header(&[0xc0], &[0_u8; 32]);
+    }
+
+    #[test]
+    fn test_evaluate_predicate() {
+        let account = Account {
+            nonce: 150,
+            balance: amount(2_000_000_000_000_000_000),
+            ..Account::empty()
+        };
+        let active_eoa = Predicate::And(vec![
+            Predicate::Geq(AccountField::Nonce, amount(100)),
+            Predicate::Geq(AccountField::Balance, amount(1_000_000_000_000_000_000)),
+            Predicate::Eq(AccountField::CodeHash, EMPTY_CODE_HASH),
+        ]);
+
+        assert!(evaluate_predicate(&account, &active_eoa));
+        assert!(!evaluate_predicate(&Account::empty(), &active_eoa));
+        assert!(evaluate_predicate(
+            &account,
+            &Predicate::Or(vec![
+                Predicate::Leq(AccountField::Nonce, amount(10)),
+                Predicate::Not(Box::new(Predicate::Eq(
+                    AccountField::StorageRoot,
+                    amount(0)
+                ))),
+            ])
+        ));
+    }
+
+    #[test]
+    fn test_check_balance_claim_at_most() {
+        assert_eq!(
+            check_balance_claim(&[], BalanceClaim::AtMost(amount(0))),
+            ProvenBalance::AtMost(amount(0))
+        );
+        assert_eq!(
+            check_balance_claim(&[1, 0], BalanceClaim::AtMost(amount(256))),
+            ProvenBalance::AtMost(amount(256))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Account balance is larger than the expected balance.")]
+    fn test_check_balance_claim_at_most_exceeded() {
+        check_balance_claim(&[1, 1], BalanceClaim::AtMost(amount(256)));
+    }
+
+    #[test]
+    fn test_check_balance_claim_equal() {
+        assert_eq!(
+            check_balance_claim(&[1, 0], BalanceClaim::Equal(amount(256))),
+            ProvenBalance::Equal(amount(256))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Account balance does not equal the expected balance.")]
+    fn test_check_balance_claim_not_equal() {
+        check_balance_claim(&[255], BalanceClaim::Equal(amount(256)));
+    }
+
+    #[test]
+    fn test_check_balance_claim_range() {
+        assert_eq!(
+            check_balance_claim(&[100], BalanceClaim::Range(amount(10), amount(100))),
+            ProvenBalance::Range(amount(10), amount(100))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Account balance is outside of the expected range.")]
+    fn test_check_balance_claim_out_of_range() {
+        check_balance_claim(&[101], BalanceClaim::Range(amount(10), amount(100)));
+    }
+
+    #[test]
+    fn test_check_balance_claim_tiers() {
+        let tiers = vec![amount(1), amount(1000), amount(10), amount(100)];
+
+        assert_eq!(
+            check_balance_claim(&[2, 0], BalanceClaim::Tiers(tiers.clone())),
+            ProvenBalance::Tier {
+                tier: amount(100),
+                tiers
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Account balance is smaller than the lowest tier.")]
+    fn test_check_balance_claim_below_tiers() {
+        check_balance_claim(&[9], BalanceClaim::Tiers(vec![amount(10), amount(100)]));
+    }
+
     #[test]
     fn test_create_eth_trie() {
         let mut trie = create_eth_trie();